@@ -0,0 +1,232 @@
+/// `--max-tokens` で出力を切り詰める際のブロックの優先度。
+/// 先にあるものほど優先度が高く、最後まで残される
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// クレートの `//!` docs
+    CrateDocs,
+    /// クレートルートのアイテム
+    TopLevelItem,
+    /// README.md
+    Readme,
+    /// docs のあるアイテムとモジュールの概要（モジュールの深さ順）
+    DocumentedItem(usize),
+    /// docs のないアイテム（モジュールの深さ順）
+    ModuleItem(usize),
+    /// examples/ のサンプル
+    Example,
+    /// Cargo.toml の内容
+    Manifest,
+}
+
+/// 出力の構成要素。`priority` が None のブロックは切り詰めの対象にならない
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub label: String,
+    pub priority: Option<Priority>,
+    pub content: String,
+    /// 見出しとなるブロックの位置。子が残る限り見出しも残す
+    pub parent: Option<usize>,
+}
+
+impl Block {
+    pub fn required(content: String) -> Self {
+        Block {
+            label: String::new(),
+            priority: None,
+            content,
+            parent: None,
+        }
+    }
+
+    pub fn optional(label: impl Into<String>, priority: Priority, content: String) -> Self {
+        Block {
+            label: label.into(),
+            priority: Some(priority),
+            content,
+            parent: None,
+        }
+    }
+}
+
+/// 切り詰めの結果
+#[derive(Debug)]
+pub struct TrimmedContent {
+    pub content: String,
+    /// 残したブロックの推定トークン数
+    pub tokens: usize,
+    /// 削除したブロックのラベル（出力順）
+    pub dropped: Vec<String>,
+}
+
+/// オフラインで使えるトークン数の概算。英数字の連続は 4 文字ごとに 1 トークン、
+/// 記号と非 ASCII 文字は 1 文字 1 トークンとして数え、空白は数えない
+pub fn estimate_tokens(text: &str) -> usize {
+    let mut tokens = 0;
+    let mut word_len: usize = 0;
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            word_len += 1;
+            continue;
+        }
+        tokens += word_len.div_ceil(4);
+        word_len = 0;
+        if !c.is_whitespace() {
+            tokens += 1;
+        }
+    }
+    tokens + word_len.div_ceil(4)
+}
+
+/// 推定トークン数が `max_tokens` に収まるまで優先度の低いブロックから削除する。
+/// 同じ優先度では後ろのブロックから削除し、子が残っている見出しは削除しない
+pub fn fit_to_budget(blocks: Vec<Block>, max_tokens: usize) -> TrimmedContent {
+    let tokens: Vec<usize> = blocks.iter().map(|b| estimate_tokens(&b.content)).collect();
+    let mut kept = vec![true; blocks.len()];
+    let mut total: usize = tokens.iter().sum();
+
+    let mut candidates: Vec<(Priority, usize)> = blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, b)| b.priority.map(|p| (p, i)))
+        .collect();
+    candidates.sort_by(|a, b| b.cmp(a));
+
+    // 見出しは子がすべて削除されてから削除できるので、変化がなくなるまで繰り返す
+    let mut changed = true;
+    while total > max_tokens && changed {
+        changed = false;
+        for &(_, i) in &candidates {
+            if total <= max_tokens {
+                break;
+            }
+            let has_kept_child = blocks
+                .iter()
+                .enumerate()
+                .any(|(j, b)| b.parent == Some(i) && kept[j]);
+            if kept[i] && !has_kept_child {
+                kept[i] = false;
+                total -= tokens[i];
+                changed = true;
+            }
+        }
+    }
+
+    let mut content = String::new();
+    let mut dropped = Vec::new();
+    for (block, kept) in blocks.into_iter().zip(kept) {
+        if kept {
+            content.push_str(&block.content);
+        } else {
+            dropped.push(block.label);
+        }
+    }
+
+    TrimmedContent {
+        content,
+        tokens: total,
+        dropped,
+    }
+}
+
+/// llms-full.txt のモジュールセクションを、見出しと概要・アイテムごとのエントリに分割する
+pub fn split_entries(content: &str) -> (&str, Vec<&str>) {
+    let mut starts: Vec<usize> = content
+        .match_indices("\n### ")
+        .map(|(i, _)| i + 1)
+        .collect();
+    let head_end = starts.first().copied().unwrap_or(content.len());
+    starts.push(content.len());
+    let entries = starts.windows(2).map(|w| &content[w[0]..w[1]]).collect();
+    (&content[..head_end], entries)
+}
+
+/// エントリの見出し（`### ` の後）を返す
+pub fn entry_title(entry: &str) -> &str {
+    entry
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('#')
+        .trim()
+}
+
+/// エントリのシグネチャの後に docs が書かれているかどうか
+pub fn entry_has_docs(entry: &str) -> bool {
+    let Some((_, after_code)) = entry.split_once("\n```\n") else {
+        return false;
+    };
+    let docs = after_code.split("\n#### ").next().unwrap_or_default();
+    !docs.trim().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("fn main"), 2);
+        assert_eq!(estimate_tokens("pub fn parse(input: &str) -> usize"), 15);
+        assert_eq!(estimate_tokens("   \n\n"), 0);
+    }
+
+    #[test]
+    fn test_fit_to_budget_drops_lowest_priority_first() {
+        let mut blocks = vec![
+            Block::required("# title\n".to_string()),
+            Block::optional("crate", Priority::CrateDocs, "crate docs\n".to_string()),
+            Block::optional(
+                "mod a",
+                Priority::DocumentedItem(1),
+                "## mod a\n".to_string(),
+            ),
+            Block::optional("a::f", Priority::ModuleItem(1), "### a::f\n".to_string()),
+            Block::optional(
+                "a::g",
+                Priority::DocumentedItem(1),
+                "### a::g docs\n".to_string(),
+            ),
+            Block::optional("Cargo.toml", Priority::Manifest, "[package]\n".to_string()),
+        ];
+        blocks[3].parent = Some(2);
+        blocks[4].parent = Some(2);
+
+        let all = estimate_tokens(
+            &blocks
+                .iter()
+                .map(|b| b.content.as_str())
+                .collect::<String>(),
+        );
+        let untouched = fit_to_budget(blocks.clone(), all);
+        assert!(untouched.dropped.is_empty());
+        assert_eq!(untouched.tokens, all);
+
+        let trimmed = fit_to_budget(blocks.clone(), all - 1);
+        assert_eq!(trimmed.dropped, vec!["Cargo.toml"]);
+
+        let trimmed = fit_to_budget(blocks.clone(), all - estimate_tokens("[package]\n") - 1);
+        assert_eq!(trimmed.dropped, vec!["a::f", "Cargo.toml"]);
+
+        // 子が残っている見出しは削除しない
+        let trimmed = fit_to_budget(blocks.clone(), estimate_tokens("# title\ncrate docs\n"));
+        assert_eq!(trimmed.content, "# title\ncrate docs\n");
+        assert_eq!(trimmed.dropped, vec!["mod a", "a::f", "a::g", "Cargo.toml"]);
+
+        // 必須のブロックは予算を超えても残す
+        let trimmed = fit_to_budget(blocks, 0);
+        assert_eq!(trimmed.content, "# title\n");
+    }
+
+    #[test]
+    fn test_split_entries() {
+        let section = "## mod a\n\nOverview\n\n### a::f\n\n```rust\nfn f()\n```\n\nDocs\n\n### a::g\n\n```rust\nfn g()\n```\n\n\n";
+        let (head, entries) = split_entries(section);
+        assert_eq!(head, "## mod a\n\nOverview\n\n");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entry_title(entries[0]), "a::f");
+        assert!(entry_has_docs(entries[0]));
+        assert_eq!(entry_title(entries[1]), "a::g");
+        assert!(!entry_has_docs(entries[1]));
+    }
+}
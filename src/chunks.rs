@@ -0,0 +1,303 @@
+use serde::Serialize;
+
+use crate::budget::{entry_title, estimate_tokens, split_entries};
+use crate::model::SkippedFileModel;
+use crate::visitors::{format_module_heading, ModuleSection};
+
+/// チャンクファイルと manifest を出力するディレクトリ
+pub const CHUNK_DIR: &str = "llms-full";
+
+/// チャンクの一覧を書き出すファイル
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// llms-full.txt の分割方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ChunkMode {
+    /// モジュールごとに 1 ファイル
+    Module,
+    /// 推定トークン数の上限ごとにモジュールをまとめる・分割する
+    Size,
+}
+
+/// `llms-full/manifest.json` の内容
+#[derive(Debug, Serialize)]
+pub struct ChunkManifest {
+    pub name: String,
+    pub chunks: Vec<ChunkEntry>,
+    /// 構文エラーのためチャンクに含まれていないファイル
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_files: Vec<SkippedFileModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChunkEntry {
+    pub id: String,
+    /// `llms-full/` からの相対パス
+    pub file: String,
+    /// チャンクに含まれるモジュールのパス（`crate` または `a::b`）
+    pub modules: Vec<String>,
+    /// チャンクに含まれるアイテムの見出し
+    pub items: Vec<String>,
+    /// 推定トークン数
+    pub tokens: usize,
+}
+
+pub struct Chunk {
+    pub entry: ChunkEntry,
+    pub content: String,
+}
+
+/// ターゲットのモジュールセクション。最初のターゲット以外はチャンク id に接頭辞が付く
+pub struct TargetSections {
+    pub prefix: Option<String>,
+    pub sections: Vec<ModuleSection>,
+}
+
+/// モジュールセクションをチャンクに分割する
+pub fn build_chunks(
+    project_name: &str,
+    targets: &[TargetSections],
+    mode: ChunkMode,
+    max_tokens: usize,
+) -> Vec<Chunk> {
+    let mut builder = ChunkBuilder {
+        title: format!("# {}\n\n", project_name),
+        chunks: Vec::new(),
+        current: None,
+    };
+
+    for target in targets {
+        for section in &target.sections {
+            if section.content.is_empty() {
+                continue;
+            }
+            match mode {
+                ChunkMode::Module => {
+                    builder.flush();
+                    let id = module_chunk_id(target.prefix.as_deref(), &section.module_path);
+                    builder.push(
+                        &id,
+                        target.prefix.as_deref(),
+                        section,
+                        &section.content,
+                        None,
+                    );
+                    builder.flush();
+                }
+                ChunkMode::Size => {
+                    builder.push_bounded(target.prefix.as_deref(), section, max_tokens)
+                }
+            }
+        }
+    }
+    builder.flush();
+
+    let mut chunks = builder.chunks;
+    if mode == ChunkMode::Size {
+        let width = chunks.len().to_string().len().max(3);
+        for (i, chunk) in chunks.iter_mut().enumerate() {
+            chunk.entry.id = format!("chunk-{:0width$}", i + 1, width = width);
+            chunk.entry.file = format!("{}.md", chunk.entry.id);
+        }
+    }
+    chunks
+}
+
+/// モジュールのチャンク id（例: `crate`, `config.parser`, `bin.cli.args`）
+fn module_chunk_id(prefix: Option<&str>, module_path: &[String]) -> String {
+    let module = if module_path.is_empty() {
+        "crate".to_string()
+    } else {
+        module_path.join(".")
+    };
+    match prefix {
+        Some(prefix) if module_path.is_empty() => prefix.to_string(),
+        Some(prefix) => format!("{}.{}", prefix, module),
+        None => module,
+    }
+}
+
+/// manifest に記録するモジュールのパス（例: `crate`, `config::parser`, `bin.cli::args`）
+fn module_display_path(prefix: Option<&str>, module_path: &[String]) -> String {
+    let root = prefix.unwrap_or("crate");
+    if module_path.is_empty() {
+        root.to_string()
+    } else if prefix.is_some() {
+        format!("{}::{}", root, module_path.join("::"))
+    } else {
+        module_path.join("::")
+    }
+}
+
+struct ChunkBuilder {
+    title: String,
+    chunks: Vec<Chunk>,
+    current: Option<Chunk>,
+}
+
+impl ChunkBuilder {
+    /// 現在のチャンクにテキストを追加する。`item` はエントリの見出し
+    fn push(
+        &mut self,
+        id: &str,
+        prefix: Option<&str>,
+        section: &ModuleSection,
+        text: &str,
+        item: Option<&str>,
+    ) {
+        let chunk = self.current.get_or_insert_with(|| Chunk {
+            entry: ChunkEntry {
+                id: id.to_string(),
+                file: format!("{}.md", id),
+                modules: Vec::new(),
+                items: Vec::new(),
+                tokens: 0,
+            },
+            content: self.title.clone(),
+        });
+
+        let module = module_display_path(prefix, &section.module_path);
+        if chunk.entry.modules.last() != Some(&module) {
+            chunk.entry.modules.push(module);
+        }
+        match item {
+            Some(item) => chunk.entry.items.push(item.to_string()),
+            None => {
+                let (_, entries) = split_entries(text);
+                chunk
+                    .entry
+                    .items
+                    .extend(entries.into_iter().map(|e| entry_title(e).to_string()));
+            }
+        }
+        chunk.content.push_str(text);
+    }
+
+    /// モジュールの見出し・概要とエントリを、上限を超えない範囲でチャンクに詰める。
+    /// 見出しは最初のエントリと同じチャンクに入れ、モジュールの途中で新しいチャンクになった
+    /// 場合は見出しを繰り返す
+    fn push_bounded(&mut self, prefix: Option<&str>, section: &ModuleSection, max_tokens: usize) {
+        let (head, entries) = split_entries(&section.content);
+        let continuation = format_module_heading(
+            &section.module_path,
+            section.target.as_deref(),
+            &section.source_file,
+        );
+
+        let mut pending_head = Some(head);
+        for entry in entries {
+            let text = match pending_head {
+                Some(head) => format!("{}{}", head, entry),
+                None => entry.to_string(),
+            };
+            if !self.fits(&text, max_tokens) {
+                self.flush();
+                if pending_head.is_none() {
+                    self.push("", prefix, section, &continuation, None);
+                }
+            }
+            if let Some(head) = pending_head.take() {
+                self.push("", prefix, section, head, None);
+            }
+            self.push("", prefix, section, entry, Some(entry_title(entry)));
+        }
+
+        if let Some(head) = pending_head {
+            if !self.fits(head, max_tokens) {
+                self.flush();
+            }
+            self.push("", prefix, section, head, None);
+        }
+    }
+
+    fn fits(&self, text: &str, max_tokens: usize) -> bool {
+        match &self.current {
+            Some(chunk) => estimate_tokens(&chunk.content) + estimate_tokens(text) <= max_tokens,
+            None => true,
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(mut chunk) = self.current.take() {
+            chunk.entry.tokens = estimate_tokens(&chunk.content);
+            self.chunks.push(chunk);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn section(module_path: &[&str], content: &str) -> ModuleSection {
+        ModuleSection {
+            module_path: module_path.iter().map(|s| s.to_string()).collect(),
+            target: None,
+            source_file: PathBuf::from("src/lib.rs"),
+            docs: None,
+            content: content.to_string(),
+            items: Vec::new(),
+        }
+    }
+
+    fn targets() -> Vec<TargetSections> {
+        vec![
+            TargetSections {
+                prefix: None,
+                sections: vec![
+                    section(
+                        &[],
+                        "## crate\n\nCrate docs\n\n### open\n\n```rust\npub fn open()\n```\n\n\n",
+                    ),
+                    section(&["hidden"], ""),
+                    section(
+                        &["storage"],
+                        "## mod storage\n\n### storage::read\n\n```rust\npub fn read()\n```\n\n### storage::write\n\n```rust\npub fn write()\n```\n\n\n",
+                    ),
+                ],
+            },
+            TargetSections {
+                prefix: Some("bin.cli".to_string()),
+                sections: vec![section(&[], "## crate\n\n### run\n\n```rust\nfn run()\n```\n\n\n")],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_build_chunks_per_module() {
+        let chunks = build_chunks("demo", &targets(), ChunkMode::Module, 0);
+
+        let ids: Vec<&str> = chunks.iter().map(|c| c.entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["crate", "storage", "bin.cli"]);
+
+        let storage = &chunks[1];
+        assert_eq!(storage.entry.file, "storage.md");
+        assert_eq!(storage.entry.modules, vec!["storage"]);
+        assert_eq!(storage.entry.items, vec!["storage::read", "storage::write"]);
+        assert!(storage.content.starts_with("# demo\n\n## mod storage\n\n"));
+        assert_eq!(storage.entry.tokens, estimate_tokens(&storage.content));
+    }
+
+    #[test]
+    fn test_build_chunks_size_bounded() {
+        let chunks = build_chunks("demo", &targets(), ChunkMode::Size, 50);
+
+        let ids: Vec<&str> = chunks.iter().map(|c| c.entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["chunk-001", "chunk-002", "chunk-003"]);
+        for chunk in &chunks {
+            assert!(chunk.entry.tokens <= 50, "{}", chunk.content);
+        }
+
+        // 小さなモジュールはまとめられ、モジュールの途中で分割したら見出しを繰り返す
+        assert_eq!(chunks[0].entry.modules, vec!["crate", "storage"]);
+        assert_eq!(chunks[0].entry.items, vec!["open", "storage::read"]);
+        assert_eq!(chunks[1].entry.modules, vec!["storage"]);
+        assert_eq!(chunks[1].entry.items, vec!["storage::write"]);
+        assert!(chunks[1].content.starts_with(
+            "# demo\n\n## mod storage\n\n*Source: `src/lib.rs`*\n\n### storage::write"
+        ));
+        assert_eq!(chunks[2].entry.modules, vec!["bin.cli"]);
+        assert_eq!(chunks[2].entry.items, vec!["run"]);
+    }
+}
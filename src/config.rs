@@ -0,0 +1,255 @@
+use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::visitors::VisibilityLevel;
+
+/// クレートごとの設定ファイル
+pub const CONFIG_FILE: &str = "llms-txt.toml";
+
+/// `[package.metadata.llms-txt]` / `[workspace.metadata.llms-txt]` / `llms-txt.toml` の設定。
+/// コマンドラインで指定された値はこれより優先される
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    pub visibility: Option<VisibilityLevel>,
+    pub include_hidden: Option<bool>,
+    /// 出力するソースファイルの glob（プロジェクトルートからの相対パス）。空ならすべて
+    pub include: Vec<String>,
+    /// 出力しないソースファイルの glob
+    pub exclude: Vec<String>,
+    pub sections: SectionsConfig,
+    pub output: OutputConfig,
+    /// llms.txt からリンクし、llms-full.txt に含める追加のドキュメント
+    pub extra_docs: Vec<ExtraDoc>,
+    /// llms-full.txt に含めるサンプルごとの最大行数
+    pub example_max_lines: Option<usize>,
+}
+
+/// 出力するセクション
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct SectionsConfig {
+    /// バージョン・作者・ライセンス・依存関係・features
+    pub project_info: bool,
+    pub core_documentation: bool,
+    pub workspace_members: bool,
+    pub table_of_contents: bool,
+    pub readme: bool,
+    pub cargo_toml: bool,
+    /// examples/ のサンプル
+    pub examples: bool,
+    /// docs のコードブロックの一覧
+    pub doc_examples: bool,
+}
+
+impl Default for SectionsConfig {
+    fn default() -> Self {
+        SectionsConfig {
+            project_info: true,
+            core_documentation: true,
+            workspace_members: true,
+            table_of_contents: true,
+            readme: true,
+            cargo_toml: true,
+            examples: true,
+            doc_examples: true,
+        }
+    }
+}
+
+/// 出力ファイルのパス（プロジェクトルートからの相対パス）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct OutputConfig {
+    pub llms: Option<PathBuf>,
+    pub full: Option<PathBuf>,
+    pub json: Option<PathBuf>,
+    /// 指定した場合のみ docs のコードブロックだけのファイルを出力する
+    pub examples: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ExtraDoc {
+    /// プロジェクトルートからの相対パス
+    pub path: PathBuf,
+    /// 省略した場合はファイル名
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+impl ExtraDoc {
+    pub fn title(&self) -> String {
+        self.title.clone().unwrap_or_else(|| {
+            self.path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+}
+
+/// 出力ファイルの実際のパス
+#[derive(Debug, Clone)]
+pub struct OutputPaths {
+    pub llms: PathBuf,
+    pub full: PathBuf,
+    pub json: PathBuf,
+    pub examples: Option<PathBuf>,
+}
+
+impl Default for OutputPaths {
+    fn default() -> Self {
+        OutputPaths {
+            llms: PathBuf::from("llms.txt"),
+            full: PathBuf::from("llms-full.txt"),
+            json: PathBuf::from("llms.json"),
+            examples: None,
+        }
+    }
+}
+
+impl OutputConfig {
+    pub fn paths(&self) -> OutputPaths {
+        let defaults = OutputPaths::default();
+        OutputPaths {
+            llms: self.llms.clone().unwrap_or(defaults.llms),
+            full: self.full.clone().unwrap_or(defaults.full),
+            json: self.json.clone().unwrap_or(defaults.json),
+            examples: self.examples.clone(),
+        }
+    }
+}
+
+/// `include` / `exclude` の glob によるソースファイルの絞り込み
+#[derive(Debug, Clone, Default)]
+pub struct FileFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl FileFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let compile = |patterns: &[String]| -> Result<Vec<Pattern>> {
+            patterns
+                .iter()
+                .map(|p| Pattern::new(p).with_context(|| format!("Invalid glob pattern: {}", p)))
+                .collect()
+        };
+        Ok(FileFilter {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    /// プロジェクトルートからの相対パスが出力対象かどうか
+    pub fn matches(&self, path: &Path) -> bool {
+        // `*` はパス区切りをまたがず、`**` でディレクトリをまたぐ
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        let matches = |pattern: &Pattern| pattern.matches_path_with(path, options);
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+}
+
+/// Cargo.toml のメタデータに `llms-txt.toml` を重ねて設定を読み込む
+pub fn load_config(project_root: &Path, metadata: toml::Table) -> Result<Config> {
+    let mut table = metadata;
+    let config_path = project_root.join(CONFIG_FILE);
+    if config_path.is_file() {
+        let content = fs::read_to_string(&config_path)?;
+        let file: toml::Table = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        merge_tables(&mut table, file);
+    }
+    Config::deserialize(toml::Value::Table(table)).context("Invalid llms-txt configuration")
+}
+
+/// `overlay` の値で `base` を上書きする。テーブルはキーごとに再帰的に統合する
+pub fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => {
+                merge_tables(base, overlay)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_config_layers() {
+        let mut table: toml::Table = toml::from_str(
+            r#"
+visibility = "crate"
+exclude = ["src/internal/**"]
+
+[sections]
+readme = false
+cargo-toml = false
+"#,
+        )
+        .unwrap();
+        let file: toml::Table = toml::from_str(
+            r#"
+visibility = "all"
+
+[sections]
+readme = true
+
+[output]
+llms = "docs/llms.txt"
+"#,
+        )
+        .unwrap();
+        merge_tables(&mut table, file);
+
+        let config = Config::deserialize(toml::Value::Table(table)).unwrap();
+        assert_eq!(config.visibility, Some(VisibilityLevel::All));
+        assert_eq!(config.exclude, vec!["src/internal/**"]);
+        assert!(config.sections.readme);
+        assert!(!config.sections.cargo_toml);
+        assert!(config.sections.table_of_contents);
+
+        let paths = config.output.paths();
+        assert_eq!(paths.llms, PathBuf::from("docs/llms.txt"));
+        assert_eq!(paths.full, PathBuf::from("llms-full.txt"));
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_keys() {
+        let table: toml::Table = toml::from_str("[sections]\nreadmee = false\n").unwrap();
+        assert!(Config::deserialize(toml::Value::Table(table)).is_err());
+    }
+
+    #[test]
+    fn test_file_filter() {
+        let filter = FileFilter::new(
+            &["src/**".to_string()],
+            &["src/internal/**".to_string(), "src/*_test.rs".to_string()],
+        )
+        .unwrap();
+        assert!(filter.matches(Path::new("src/lib.rs")));
+        assert!(filter.matches(Path::new("src/net/addr.rs")));
+        assert!(!filter.matches(Path::new("src/internal/cache.rs")));
+        assert!(!filter.matches(Path::new("src/io_test.rs")));
+        assert!(filter.matches(Path::new("src/net/io_test.rs")));
+        assert!(!filter.matches(Path::new("build.rs")));
+
+        assert!(FileFilter::default().matches(Path::new("build.rs")));
+        assert!(FileFilter::new(&["src/[".to_string()], &[]).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+/// docs 中の Rust のコードブロック（doctest）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocExample {
+    /// `# ` で始まる隠し行を取り除いたコード
+    pub code: String,
+    /// `ignore` / `no_run` / `should_panic` / `compile_fail`
+    pub attributes: Vec<&'static str>,
+}
+
+impl DocExample {
+    /// テストとしての扱いの注記
+    pub fn notes(&self) -> Vec<&'static str> {
+        self.attributes
+            .iter()
+            .map(|attr| match *attr {
+                "ignore" => "`ignore`: not compiled or run as a test",
+                "no_run" => "`no_run`: compiled but not run",
+                "should_panic" => "`should_panic`: expected to panic",
+                _ => "`compile_fail`: expected to fail to compile",
+            })
+            .collect()
+    }
+}
+
+/// 出力で注記する doctest の属性
+const MARKED_ATTRIBUTES: [&str; 4] = ["ignore", "no_run", "should_panic", "compile_fail"];
+
+/// docs からコードブロックを順に取り出す。rustdoc と同じく、言語の指定がないか
+/// rustdoc の属性だけのコードブロックを Rust として扱う
+pub fn extract_doc_examples(docs: &str) -> Vec<DocExample> {
+    let mut examples = Vec::new();
+    let mut lines = docs.lines();
+    while let Some(line) = lines.next() {
+        let Some((fence, info)) = parse_fence(line) else {
+            continue;
+        };
+
+        let mut code = Vec::new();
+        for line in lines.by_ref() {
+            if is_closing_fence(line, fence) {
+                break;
+            }
+            code.push(line);
+        }
+
+        if let Some(attributes) = rust_block_attributes(info) {
+            examples.push(DocExample {
+                code: strip_hidden_lines(&code),
+                attributes,
+            });
+        }
+    }
+    examples
+}
+
+/// コードブロックの開始行ならフェンスと info 文字列を返す
+pub fn parse_fence(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    let c = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|ch| *ch == c).count();
+    (len >= 3).then(|| (&trimmed[..len], trimmed[len..].trim()))
+}
+
+fn fence_char(fence: &str) -> char {
+    fence.chars().next().unwrap_or('`')
+}
+
+/// `fence` で始まったコードブロックの終了行かどうか
+pub fn is_closing_fence(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with(fence) && trimmed.trim_end().chars().all(|c| c == fence_char(fence))
+}
+
+/// info 文字列から rustdoc が Rust として扱うコードブロックかどうかを判定する
+pub fn is_rust_block(info: &str) -> bool {
+    rust_block_attributes(info).is_some()
+}
+
+/// Rust のコードブロックなら注記する属性を返す
+fn rust_block_attributes(info: &str) -> Option<Vec<&'static str>> {
+    let mut attributes = Vec::new();
+    for token in info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+    {
+        // `ignore-wasm32` などのターゲット指定も ignore として扱う
+        let token = if token.starts_with("ignore-") {
+            "ignore"
+        } else {
+            token
+        };
+        if let Some(attr) = MARKED_ATTRIBUTES.iter().find(|attr| **attr == token) {
+            if !attributes.contains(attr) {
+                attributes.push(*attr);
+            }
+        } else if !(token == "rust" || token == "test_harness" || token.starts_with("edition")) {
+            return None;
+        }
+    }
+    Some(attributes)
+}
+
+/// rustdoc と同じく `# ` で始まる行と `#` だけの行を取り除き、`##` を `#` に戻す
+pub fn strip_hidden_lines(lines: &[&str]) -> String {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("##") {
+                Some(line.replacen("##", "#", 1))
+            } else if trimmed.starts_with("# ") || trimmed == "#" {
+                None
+            } else {
+                Some(line.to_string())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_doc_examples() {
+        let docs = "Adds numbers.\n\
+                    \n\
+                    # Examples\n\
+                    \n\
+                    ```\n\
+                    # use demo::add;\n\
+                    # fn main() {\n\
+                    assert_eq!(add(1, 2), 3);\n\
+                    ## not hidden\n\
+                    # }\n\
+                    ```\n\
+                    \n\
+                    ```toml\n\
+                    [dependencies]\n\
+                    ```\n\
+                    \n\
+                    ```rust,no_run\n\
+                    demo::serve();\n\
+                    ```\n\
+                    \n\
+                    ~~~should_panic, ignore-wasm32\n\
+                    demo::fail();\n\
+                    ~~~\n";
+        let examples = extract_doc_examples(docs);
+        assert_eq!(
+            examples,
+            vec![
+                DocExample {
+                    code: "assert_eq!(add(1, 2), 3);\n# not hidden".to_string(),
+                    attributes: vec![],
+                },
+                DocExample {
+                    code: "demo::serve();".to_string(),
+                    attributes: vec!["no_run"],
+                },
+                DocExample {
+                    code: "demo::fail();".to_string(),
+                    attributes: vec!["should_panic", "ignore"],
+                },
+            ]
+        );
+        assert_eq!(
+            examples[2].notes(),
+            vec![
+                "`should_panic`: expected to panic",
+                "`ignore`: not compiled or run as a test"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_doc_examples_keeps_attributes() {
+        // `#[derive]` などの属性は隠し行ではない
+        let docs = "```edition2021\n#[derive(Debug)]\nstruct A;\n#\n```";
+        assert_eq!(
+            extract_doc_examples(docs)[0].code,
+            "#[derive(Debug)]\nstruct A;"
+        );
+    }
+}
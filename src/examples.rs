@@ -0,0 +1,225 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::project_info::ProjectInfo;
+use crate::visitors::{doc_lines, doc_summary};
+
+/// `examples/` のサンプルプログラム
+#[derive(Debug, Clone)]
+pub struct Example {
+    /// `cargo run --example` に渡す名前
+    pub name: String,
+    /// プロジェクトルートからの相対パス
+    pub relative_path: PathBuf,
+    /// ファイル先頭の `//!` docs
+    pub docs: Option<String>,
+    /// docs の最初の段落
+    pub summary: Option<String>,
+    /// 先頭の `//!` docs を除いたソース
+    pub source: String,
+}
+
+/// `[[example]]` と `examples/` 以下の規約上のパスからサンプルを列挙する
+pub fn discover_examples(project_root: &Path, project_info: &ProjectInfo) -> Result<Vec<Example>> {
+    let mut targets: Vec<(String, PathBuf)> = Vec::new();
+
+    // Cargo.toml で宣言されたサンプル
+    for example in &project_info.targets.examples {
+        let path = match (&example.path, &example.name) {
+            (Some(path), _) => Some(PathBuf::from(path)),
+            (None, Some(name)) => [
+                PathBuf::from(format!("examples/{}.rs", name)),
+                PathBuf::from(format!("examples/{}/main.rs", name)),
+            ]
+            .into_iter()
+            .find(|p| project_root.join(p).is_file()),
+            (None, None) => None,
+        };
+        if let Some(path) = path {
+            let name = example.name.clone().unwrap_or_else(|| example_name(&path));
+            push_example_target(&mut targets, project_root, name, path);
+        }
+    }
+
+    // 規約に基づくサンプルの自動検出
+    if project_info.targets.autoexamples {
+        if let Ok(entries) = fs::read_dir(project_root.join("examples")) {
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(|e| e.ok())
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let example_path = PathBuf::from("examples").join(path.file_name()?);
+                    if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+                        Some(example_path)
+                    } else if path.join("main.rs").is_file() {
+                        Some(example_path.join("main.rs"))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            paths.sort();
+            for path in paths {
+                push_example_target(&mut targets, project_root, example_name(&path), path);
+            }
+        }
+    }
+
+    targets
+        .into_iter()
+        .map(|(name, relative_path)| {
+            let full_path = project_root.join(&relative_path);
+            let source = fs::read_to_string(&full_path)
+                .with_context(|| format!("Failed to read {}", full_path.display()))?;
+            // 構文エラーのあるサンプルも docs なしでコードは出力する
+            let attrs = syn::parse_file(&source)
+                .map(|file| file.attrs)
+                .unwrap_or_default();
+            let docs = doc_lines(&attrs).join("\n").trim().to_string();
+            Ok(Example {
+                name,
+                relative_path,
+                docs: (!docs.is_empty()).then_some(docs),
+                summary: doc_summary(&attrs, full_path.parent()),
+                source: strip_leading_inner_docs(&source),
+            })
+        })
+        .collect()
+}
+
+fn push_example_target(
+    targets: &mut Vec<(String, PathBuf)>,
+    project_root: &Path,
+    name: String,
+    path: PathBuf,
+) {
+    if !project_root.join(&path).is_file() || targets.iter().any(|(_, p)| *p == path) {
+        return;
+    }
+    targets.push((name, path));
+}
+
+/// `examples/foo.rs` と `examples/foo/main.rs` のサンプル名はどちらも `foo`
+fn example_name(path: &Path) -> String {
+    let path = if path.ends_with("main.rs") {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+    path.file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// docs は別に出力するので、ソース先頭の `//!` コメントと続く空行を取り除く
+fn strip_leading_inner_docs(source: &str) -> String {
+    let mut lines = source.lines().peekable();
+    let mut stripped = false;
+    while lines
+        .peek()
+        .is_some_and(|line| line.trim_start().starts_with("//!"))
+    {
+        lines.next();
+        stripped = true;
+    }
+    if !stripped {
+        return source.to_string();
+    }
+    while lines.peek().is_some_and(|line| line.trim().is_empty()) {
+        lines.next();
+    }
+    lines.map(|line| format!("{}\n", line)).collect()
+}
+
+/// サンプルのソースを最大 `max_lines` 行に切り詰める。切り詰めた場合は省略した行数を返す
+pub fn truncate_source(source: &str, max_lines: usize) -> (String, Option<usize>) {
+    let total = source.lines().count();
+    if total <= max_lines {
+        return (source.trim_end().to_string(), None);
+    }
+    let kept = source
+        .lines()
+        .take(max_lines)
+        .collect::<Vec<_>>()
+        .join("\n");
+    (kept, Some(total - max_lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_info::TargetInfo;
+
+    fn write_file(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_discover_examples() {
+        let temp_dir = std::env::temp_dir().join("cargo_llms_txt_test_examples");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        write_file(
+            &temp_dir,
+            "examples/basic.rs",
+            "//! Basic usage.\n//!\n//! Longer explanation.\n\nfn main() {}\n",
+        );
+        write_file(&temp_dir, "examples/server/main.rs", "fn main() {}\n");
+        write_file(&temp_dir, "examples/server/util.rs", "pub fn helper() {}\n");
+        write_file(
+            &temp_dir,
+            "demos/custom.rs",
+            "//! Custom path.\nfn main() {}\n",
+        );
+
+        let mut info = ProjectInfo::default();
+        info.targets.examples = vec![TargetInfo {
+            name: Some("custom".to_string()),
+            path: Some("demos/custom.rs".to_string()),
+        }];
+
+        let examples = discover_examples(&temp_dir, &info).unwrap();
+        let names: Vec<_> = examples
+            .iter()
+            .map(|e| (e.name.as_str(), e.relative_path.clone()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("custom", PathBuf::from("demos/custom.rs")),
+                ("basic", PathBuf::from("examples/basic.rs")),
+                ("server", PathBuf::from("examples/server/main.rs")),
+            ]
+        );
+        assert_eq!(
+            examples[1].docs.as_deref(),
+            Some("Basic usage.\n\nLonger explanation.")
+        );
+        assert_eq!(examples[1].summary.as_deref(), Some("Basic usage."));
+        assert_eq!(examples[1].source, "fn main() {}\n");
+        assert_eq!(examples[2].docs, None);
+
+        // autoexamples = false では宣言されたサンプルのみ
+        info.targets.autoexamples = false;
+        assert_eq!(discover_examples(&temp_dir, &info).unwrap().len(), 1);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_truncate_source() {
+        let source = "fn main() {\n    one();\n    two();\n}\n";
+        assert_eq!(
+            truncate_source(source, 10),
+            (source.trim_end().to_string(), None)
+        );
+        assert_eq!(
+            truncate_source(source, 2),
+            ("fn main() {\n    one();".to_string(), Some(2))
+        );
+    }
+}
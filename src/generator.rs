@@ -1,107 +1,536 @@
-use anyhow::Result;
-use chrono::Utc;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use std::fs;
 use std::path::{Path, PathBuf};
 use syn::visit::Visit;
-use walkdir::WalkDir;
 
+use crate::budget::{entry_has_docs, entry_title, fit_to_budget, split_entries, Block, Priority};
+use crate::chunks::{
+    build_chunks, ChunkManifest, ChunkMode, TargetSections, CHUNK_DIR, MANIFEST_FILE,
+};
+use crate::config::{ExtraDoc, FileFilter, OutputPaths, SectionsConfig};
+use crate::doctests::{extract_doc_examples, DocExample};
+use crate::examples::{discover_examples, truncate_source, Example};
+use crate::impls::ImplIndex;
+use crate::model::{CrateModel, ModuleModel, SkippedFileModel};
+use crate::modules::{CrateSources, ModuleFile, SkippedFile, TargetKind};
+use crate::output::OutputWriter;
 use crate::project_info::ProjectInfo;
-use crate::visitors::{CompleteDocsVisitor, TocVisitor};
+use crate::public_api::PublicApi;
+use crate::visitors::{
+    doc_summary, module_label, CompleteDocsVisitor, ModuleSection, TocVisitor, VisibilityLevel,
+};
 
 /// TOCアイテムの型定義
 type TocItems = Vec<(PathBuf, Vec<String>)>;
 
+/// `--max-tokens` で切り詰めた際に末尾に追加する注記の推定トークン数
+const TRIM_NOTE_TOKENS: usize = 32;
+
+/// コマンドラインから指定される生成設定
+#[derive(Debug, Clone, Default)]
+pub struct GeneratorSettings {
+    /// 出力するアイテムの可視性レベル
+    pub visibility: VisibilityLevel,
+    /// `#[doc(hidden)]` のアイテムも出力するかどうか
+    pub include_hidden: bool,
+    /// 出力ファイルごとの推定トークン数の上限
+    pub max_tokens: Option<usize>,
+    /// llms-full.txt の代わりにチャンクファイルを出力する場合の分割方法
+    pub chunks: Option<ChunkMode>,
+    /// `ChunkMode::Size` でのチャンクごとの推定トークン数の上限
+    pub chunk_tokens: usize,
+    /// `Generated:` 行に出力する日時。None の場合は行を出力しない
+    pub timestamp: Option<DateTime<Utc>>,
+    /// 出力するソースファイル
+    pub files: FileFilter,
+    /// 出力するセクション
+    pub sections: SectionsConfig,
+    /// 出力ファイルのパス
+    pub outputs: OutputPaths,
+    /// llms.txt からリンクし、llms-full.txt に含める追加のドキュメント
+    pub extra_docs: Vec<ExtraDoc>,
+    /// 構文エラーのあるファイルを読み飛ばさずにエラーにする
+    pub strict: bool,
+    /// llms-full.txt に含めるサンプルごとの最大行数。None の場合は切り詰めない
+    pub example_max_lines: Option<usize>,
+}
+
+/// `Generated:` 行の日時を決める。
+/// `--no-timestamp`、`--timestamp`、`SOURCE_DATE_EPOCH`、現在時刻の順に優先する
+pub fn resolve_timestamp(
+    no_timestamp: bool,
+    pinned: Option<i64>,
+    source_date_epoch: Option<&str>,
+) -> Result<Option<DateTime<Utc>>> {
+    if no_timestamp {
+        return Ok(None);
+    }
+    let seconds = match (pinned, source_date_epoch) {
+        (Some(seconds), _) => seconds,
+        (None, Some(epoch)) => epoch
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid SOURCE_DATE_EPOCH: {:?}", epoch))?,
+        (None, None) => return Ok(Some(Utc::now())),
+    };
+    DateTime::from_timestamp(seconds, 0)
+        .map(Some)
+        .with_context(|| format!("Timestamp out of range: {}", seconds))
+}
+
 /// 共通の生成オプション
 #[derive(Clone)]
 struct GenerationOptions {
     include_core_docs: bool,
+    include_workspace_members: bool,
     include_cargo_toml: bool,
     include_complete_api: bool,
     title_suffix: Option<&'static str>,
+    /// プロジェクトルートからの出力先
+    output: PathBuf,
 }
 
-pub fn generate_llms_txt(project_root: &Path, project_info: &ProjectInfo) -> Result<()> {
+pub fn generate_llms_txt(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    sources: &CrateSources,
+    settings: &GeneratorSettings,
+    out: &mut OutputWriter,
+) -> Result<()> {
     let options = GenerationOptions {
-        include_core_docs: true,
-        include_cargo_toml: true,
+        include_core_docs: settings.sections.core_documentation,
+        include_workspace_members: settings.sections.workspace_members,
+        include_cargo_toml: settings.sections.cargo_toml,
         include_complete_api: false,
         title_suffix: None,
+        output: settings.outputs.llms.clone(),
     };
 
-    let content = generate_common_content(project_root, project_info, &options)?;
-    fs::write(project_root.join("llms.txt"), content)?;
+    let content = generate_common_content(project_root, project_info, sources, &options, settings)?;
+    out.write(&project_root.join(&options.output), &content)?;
     Ok(())
 }
 
-pub fn generate_llms_full_txt(project_root: &Path, project_info: &ProjectInfo) -> Result<()> {
+pub fn generate_llms_full_txt(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    sources: &CrateSources,
+    settings: &GeneratorSettings,
+    out: &mut OutputWriter,
+) -> Result<()> {
     let options = GenerationOptions {
         include_core_docs: false,
+        include_workspace_members: false,
         include_cargo_toml: false,
         include_complete_api: true,
         title_suffix: Some(" - Complete API Documentation"),
+        output: settings.outputs.full.clone(),
     };
 
-    let content = generate_common_content(project_root, project_info, &options)?;
-    fs::write(project_root.join("llms-full.txt"), content)?;
+    if let Some(mode) = settings.chunks {
+        return generate_chunks(project_root, project_info, sources, mode, settings, out);
+    }
+
+    let content = generate_common_content(project_root, project_info, sources, &options, settings)?;
+    out.write(&project_root.join(&options.output), &content)?;
     Ok(())
 }
 
-fn generate_common_content(
+/// `output.examples` が指定されている場合、docs のコードブロックだけを集めたファイルを出力する
+pub fn generate_doc_examples(
     project_root: &Path,
     project_info: &ProjectInfo,
-    options: &GenerationOptions,
-) -> Result<String> {
-    let mut content = String::new();
+    sources: &CrateSources,
+    settings: &GeneratorSettings,
+    out: &mut OutputWriter,
+) -> Result<()> {
+    let Some(output) = &settings.outputs.examples else {
+        return Ok(());
+    };
+
+    let module_files = &sources.module_files;
+    let public_api = build_public_api(module_files);
+    let sections =
+        collect_module_sections(project_root, module_files, public_api.as_ref(), settings);
+
+    let mut content = format!(
+        "# {} - Doc Examples\n\n",
+        project_name(project_root, project_info)
+    );
+    if let Some(description) = project_info
+        .description
+        .clone()
+        .or_else(|| crate_root_summary(project_root, module_files))
+    {
+        content.push_str(&format!("> {}\n\n", description));
+    }
+    for (label, examples) in collect_doc_examples(&sections) {
+        content.push_str(&format_doc_examples("##", &label, &examples));
+    }
+
+    out.write(
+        &project_root.join(output),
+        &format!("{}\n", content.trim_end()),
+    )?;
+    Ok(())
+}
+
+/// 抽出した API のモデルを llms.json として出力する
+pub fn generate_llms_json(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    sources: &CrateSources,
+    settings: &GeneratorSettings,
+    out: &mut OutputWriter,
+) -> Result<()> {
+    let model = build_crate_model(project_root, project_info, sources, settings)?;
+    let mut json = serde_json::to_string_pretty(&model)?;
+    json.push('\n');
+    out.write(&project_root.join(&settings.outputs.json), &json)?;
+    Ok(())
+}
+
+/// llms-full.txt と同じ抽出結果からクレートのモデルを組み立てる
+fn build_crate_model(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    sources: &CrateSources,
+    settings: &GeneratorSettings,
+) -> Result<CrateModel> {
+    let CrateSources {
+        module_files,
+        skipped,
+    } = sources;
+    let public_api = build_public_api(module_files);
+
+    let description = project_info
+        .description
+        .clone()
+        .or_else(|| crate_root_summary(project_root, module_files));
+
+    let modules =
+        collect_module_sections(project_root, module_files, public_api.as_ref(), settings)
+            .into_iter()
+            // llms-full.txt に出力されないモジュールは含めない
+            .filter(|section| !section.content.is_empty())
+            .map(|section| ModuleModel {
+                path: if section.module_path.is_empty() {
+                    "crate".to_string()
+                } else {
+                    section.module_path.join("::")
+                },
+                target: section.target,
+                source_file: to_link_path(&section.source_file),
+                docs: section.docs,
+                items: section.items,
+            })
+            .collect();
+
+    Ok(CrateModel {
+        name: project_name(project_root, project_info).to_string(),
+        version: project_info.version.clone(),
+        description,
+        modules,
+        skipped_files: skipped_file_models(skipped),
+    })
+}
+
+fn skipped_file_models(skipped: &[SkippedFile]) -> Vec<SkippedFileModel> {
+    skipped
+        .iter()
+        .map(|file| SkippedFileModel {
+            file: to_link_path(&file.relative_path),
+            line: file.line,
+            column: file.column,
+            message: file.message.clone(),
+        })
+        .collect()
+}
 
-    // プロジェクト名を取得（デフォルトはディレクトリ名）
-    let project_name = project_info.name.as_deref().unwrap_or_else(|| {
+/// プロジェクト名を取得（デフォルトはディレクトリ名）
+fn project_name<'a>(project_root: &'a Path, project_info: &'a ProjectInfo) -> &'a str {
+    project_info.name.as_deref().unwrap_or_else(|| {
         project_root
             .file_name()
             .unwrap_or_default()
             .to_str()
             .unwrap_or("unknown")
-    });
+    })
+}
+
+/// ライブラリの場合は再エクスポートを考慮した公開APIを計算
+fn build_public_api(module_files: &[ModuleFile]) -> Option<PublicApi> {
+    module_files
+        .iter()
+        .any(|f| f.kind == TargetKind::Lib)
+        .then(|| PublicApi::build(module_files.iter().filter(|f| f.kind == TargetKind::Lib)))
+}
+
+/// 仮想ワークスペースのルートに各メンバーへのインデックスとなる llms.txt を生成する
+pub fn generate_workspace_index(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    settings: &GeneratorSettings,
+    out: &mut OutputWriter,
+) -> Result<()> {
+    let mut content = String::new();
+
+    let workspace_name = project_root
+        .canonicalize()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "workspace".to_string());
+    content.push_str(&format!("# {}\n\n", workspace_name));
+
+    content.push_str(&format_project_info(
+        project_info,
+        settings.sections.project_info,
+        settings.timestamp,
+    )?);
+    if settings.sections.workspace_members {
+        content.push_str(&format_workspace_members_section(
+            project_root,
+            project_info,
+            settings,
+        ));
+    }
+
+    content.push_str("---\n\n");
+    if settings.sections.readme {
+        content.push_str(&format_readme_section(project_root)?);
+    }
+    if settings.sections.cargo_toml {
+        content.push_str(&format_cargo_toml_section(project_root)?);
+    }
+
+    out.write(&project_root.join(&settings.outputs.llms), &content)?;
+    Ok(())
+}
+
+fn generate_common_content(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    sources: &CrateSources,
+    options: &GenerationOptions,
+    settings: &GeneratorSettings,
+) -> Result<String> {
+    let mut header = String::new();
+
+    let project_name = project_name(project_root, project_info);
 
     // ヘッダー
     let title = match options.title_suffix {
         Some(suffix) => format!("# {}{}\n\n", project_name, suffix),
         None => format!("# {}\n\n", project_name),
     };
-    content.push_str(&title);
+    header.push_str(&title);
+
+    let CrateSources {
+        module_files,
+        skipped,
+    } = sources;
+
+    // Cargo.toml に description がない場合はクレートルートの `//!` を概要として使う
+    if project_info.description.is_none() {
+        if let Some(summary) = crate_root_summary(project_root, module_files) {
+            header.push_str(&format!("> {}\n\n", summary));
+        }
+    }
 
     // プロジェクト情報を出力
-    content.push_str(&format_project_info(project_info)?);
+    header.push_str(&format_project_info(
+        project_info,
+        settings.sections.project_info,
+        settings.timestamp,
+    )?);
 
-    // Core Documentation（llms.txtのみ）
+    // Core Documentation と Workspace Members（llms.txtのみ）
     if options.include_core_docs {
-        content.push_str(&format_core_documentation_section(project_root));
+        header.push_str(&format_core_documentation_section(project_root, settings));
     }
+    if options.include_workspace_members {
+        header.push_str(&format_workspace_members_section(
+            project_root,
+            project_info,
+            settings,
+        ));
+    }
+
+    let mut blocks = vec![Block::required(header)];
+
+    let public_api = build_public_api(module_files);
 
     // Table of Contents
-    let (toc_content, toc_items) = generate_table_of_contents(project_root)?;
-    content.push_str(&toc_content);
+    let (toc_head, toc_blocks, toc_items) =
+        generate_table_of_contents(project_root, module_files, public_api.as_ref(), settings)?;
+    if settings.sections.table_of_contents {
+        push_block_group(&mut blocks, toc_head, toc_blocks);
+    }
 
-    content.push_str("---\n\n");
+    let examples = if settings.sections.examples {
+        discover_examples(project_root, project_info)?
+    } else {
+        Vec::new()
+    };
+
+    // サンプルの一覧（llms.txtのみ）
+    if !options.include_complete_api && !examples.is_empty() {
+        blocks.push(Block::optional(
+            "Examples",
+            Priority::Example,
+            format_examples_list(project_root, &options.output, &examples),
+        ));
+    }
+
+    // 構文エラーで読み飛ばしたファイルは、ドキュメントが欠けていることが分かるように残す
+    if !skipped.is_empty() {
+        blocks.push(Block::required(format_skipped_files_section(skipped)));
+    }
+
+    blocks.push(Block::required("---\n\n".to_string()));
 
     // README.mdの内容を含める
-    content.push_str(&format_readme_section(project_root)?);
+    if settings.sections.readme {
+        let mut readme = format_readme_section(project_root)?;
+        if !readme.is_empty() {
+            // llms-full.txtの場合はREADME.mdの後にセパレータを追加
+            if options.include_complete_api {
+                readme.push_str("---\n\n");
+            }
+            blocks.push(Block::optional("README.md", Priority::Readme, readme));
+        }
+    }
+
+    // 追加のドキュメントの内容を含める（llms-full.txtのみ）
+    if options.include_complete_api {
+        for doc in &settings.extra_docs {
+            let mut content = format_extra_doc_section(project_root, doc)?;
+            content.push_str("---\n\n");
+            blocks.push(Block::optional(
+                to_link_path(&doc.path),
+                Priority::Readme,
+                content,
+            ));
+        }
+    }
 
     // Cargo.tomlの内容を含める（llms.txtのみ）
     if options.include_cargo_toml {
-        content.push_str(&format_cargo_toml_section(project_root)?);
+        let cargo_toml = format_cargo_toml_section(project_root)?;
+        if !cargo_toml.is_empty() {
+            blocks.push(Block::optional(
+                "Cargo.toml",
+                Priority::Manifest,
+                cargo_toml,
+            ));
+        }
     }
 
     // 完全なAPIドキュメント（llms-full.txtのみ）
     if options.include_complete_api {
-        content.push_str(&format_complete_api_docs(project_root, &toc_items)?);
+        let sections =
+            collect_module_sections(project_root, module_files, public_api.as_ref(), settings);
+        format_complete_api_docs(&mut blocks, &sections, &toc_items)?;
+
+        let doc_examples = if settings.sections.doc_examples {
+            collect_doc_examples(&sections)
+        } else {
+            Vec::new()
+        };
+        if !doc_examples.is_empty() {
+            let head = Block::optional(
+                "Doc Examples",
+                Priority::Example,
+                "## Doc Examples\n\nCode examples from the documentation comments, without the lines hidden by rustdoc.\n\n".to_string(),
+            );
+            let children = doc_examples
+                .iter()
+                .map(|(label, examples)| {
+                    Block::optional(
+                        format!("Doc examples: {}", label),
+                        Priority::Example,
+                        format_doc_examples("###", label, examples),
+                    )
+                })
+                .collect();
+            push_block_group(&mut blocks, head, children);
+        }
+
+        if !examples.is_empty() {
+            let head =
+                Block::optional("Examples", Priority::Example, "## Examples\n\n".to_string());
+            let children = examples
+                .iter()
+                .map(|example| {
+                    Block::optional(
+                        format!("Example: {}", example.name),
+                        Priority::Example,
+                        format_example(example, settings.example_max_lines),
+                    )
+                })
+                .collect();
+            push_block_group(&mut blocks, head, children);
+        }
     }
 
-    Ok(content)
+    Ok(render_blocks(
+        blocks,
+        settings.max_tokens,
+        &project_root.join(&options.output),
+    ))
+}
+
+/// 見出しのブロックと、それに属するブロックを追加する
+fn push_block_group(blocks: &mut Vec<Block>, head: Block, children: Vec<Block>) {
+    let parent = blocks.len();
+    blocks.push(head);
+    blocks.extend(children.into_iter().map(|mut child| {
+        child.parent = Some(parent);
+        child
+    }));
+}
+
+/// ブロックを連結する。`--max-tokens` が指定されていれば推定トークン数が収まるように
+/// 優先度の低いブロックから削除し、削除した内容を報告する
+fn render_blocks(blocks: Vec<Block>, max_tokens: Option<usize>, output_path: &Path) -> String {
+    let Some(max_tokens) = max_tokens else {
+        return blocks.into_iter().map(|b| b.content).collect();
+    };
+
+    // 省略の注記の分を残しておく
+    let trimmed = fit_to_budget(blocks, max_tokens.saturating_sub(TRIM_NOTE_TOKENS));
+    if trimmed.dropped.is_empty() {
+        return trimmed.content;
+    }
+
+    eprintln!(
+        "{}: trimmed to ~{} tokens (--max-tokens {}), dropped {} sections:",
+        output_path.display(),
+        trimmed.tokens,
+        max_tokens,
+        trimmed.dropped.len()
+    );
+    for label in &trimmed.dropped {
+        eprintln!("  - {}", label);
+    }
+
+    let mut content = trimmed.content;
+    content.push_str(&format!(
+        "> **Note:** {} sections were omitted to fit within {} tokens.\n",
+        trimmed.dropped.len(),
+        max_tokens
+    ));
+    content
 }
 
 // ヘルパー関数群
 
-fn format_project_info(project_info: &ProjectInfo) -> Result<String> {
+fn format_project_info(
+    project_info: &ProjectInfo,
+    include_details: bool,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<String> {
     let mut content = String::new();
 
     if let Some(description) = &project_info.description {
@@ -109,9 +538,10 @@ fn format_project_info(project_info: &ProjectInfo) -> Result<String> {
     }
 
     // プロジェクト詳細情報
-    if project_info.version.is_some()
-        || project_info.authors.is_some()
-        || project_info.license.is_some()
+    if include_details
+        && (project_info.version.is_some()
+            || project_info.authors.is_some()
+            || project_info.license.is_some())
     {
         if let Some(version) = &project_info.version {
             content.push_str(&format!("**Version:** {}\n", version));
@@ -159,57 +589,307 @@ fn format_project_info(project_info: &ProjectInfo) -> Result<String> {
         content.push('\n');
     }
 
-    content.push_str(&format!(
-        "Generated: {} UTC  \n",
-        Utc::now().format("%Y-%m-%d %H:%M:%S")
-    ));
+    if let Some(timestamp) = timestamp {
+        content.push_str(&format!(
+            "Generated: {} UTC  \n",
+            timestamp.format("%Y-%m-%d %H:%M:%S")
+        ));
+    }
     content.push_str("Created by: [cargo-llms-txt](https://github.com/masinc/cargo-llms-txt)\n\n");
 
     Ok(content)
 }
 
-fn format_core_documentation_section(project_root: &Path) -> String {
+fn format_core_documentation_section(project_root: &Path, settings: &GeneratorSettings) -> String {
+    let llms = &settings.outputs.llms;
+    let full_docs = relative_link(
+        project_root,
+        llms,
+        &full_docs_path(&settings.outputs, settings),
+    );
+
     let mut content = String::new();
     content.push_str("## Core Documentation\n\n");
-    content.push_str("- [Complete API Documentation](llms-full.txt): Full public API documentation with detailed descriptions\n");
+    content.push_str(&format!("- [Complete API Documentation]({}): Full public API documentation with detailed descriptions\n", full_docs));
 
     if project_root.join("README.md").exists() {
-        content.push_str("- [README](README.md): Project overview and getting started guide\n");
+        content.push_str(&format!(
+            "- [README]({}): Project overview and getting started guide\n",
+            relative_link(project_root, llms, Path::new("README.md"))
+        ));
     }
     if project_root.join("Cargo.toml").exists() {
-        content.push_str("- [Cargo.toml](Cargo.toml): Project configuration and dependencies\n");
+        content.push_str(&format!(
+            "- [Cargo.toml]({}): Project configuration and dependencies\n",
+            relative_link(project_root, llms, Path::new("Cargo.toml"))
+        ));
+    }
+    if let Some(examples) = &settings.outputs.examples {
+        content.push_str(&format!(
+            "- [Doc Examples]({}): Code examples from the documentation comments\n",
+            relative_link(project_root, llms, examples)
+        ));
+    }
+    for doc in &settings.extra_docs {
+        let mut line = format!(
+            "- [{}]({})",
+            doc.title(),
+            relative_link(project_root, llms, &doc.path)
+        );
+        if let Some(description) = &doc.description {
+            line.push_str(&format!(": {}", description));
+        }
+        content.push_str(&format!("{}\n", line));
     }
     content.push('\n');
     content
 }
 
-fn generate_table_of_contents(project_root: &Path) -> Result<(String, TocItems)> {
+fn format_workspace_members_section(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    settings: &GeneratorSettings,
+) -> String {
+    let Some(workspace) = &project_info.workspace else {
+        return String::new();
+    };
+    if workspace.members.is_empty() {
+        return String::new();
+    }
+
     let mut content = String::new();
-    content.push_str("## Table of Contents\n\n");
+    content.push_str("## Workspace Members\n\n");
+
+    for member in &workspace.members {
+        let member_path = to_link_path(&member.path);
+        let member_name = member.info.name.as_deref().unwrap_or(&member_path);
+
+        let mut line = format!("- **{}**", member_name);
+        if let Some(version) = &member.info.version {
+            line.push_str(&format!(" ({})", version));
+        }
+        if let Some(description) = &member.info.description {
+            line.push_str(&format!(": {}", description));
+        }
+        content.push_str(&format!("{}\n", line));
+
+        // メンバーの出力先はメンバー自身の設定に従う
+        let outputs = member.info.config.output.paths();
+        let full_docs = full_docs_path(&outputs, settings);
+        // `--output-dir` で絶対パスになった場合はファイル名だけを表示する
+        let label = |path: &Path| match path.is_absolute() {
+            true => path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned(),
+            false => to_link_path(path),
+        };
+        content.push_str(&format!(
+            "  - [{}]({}): API overview\n",
+            label(&outputs.llms),
+            relative_link(
+                project_root,
+                &settings.outputs.llms,
+                &member.path.join(&outputs.llms)
+            )
+        ));
+        content.push_str(&format!(
+            "  - [{}]({}): Complete API documentation\n",
+            label(&full_docs),
+            relative_link(
+                project_root,
+                &settings.outputs.llms,
+                &member.path.join(&full_docs)
+            )
+        ));
+    }
+    content.push('\n');
+    content
+}
+
+/// Markdownのリンク用にパス区切りを `/` に統一する
+fn to_link_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// `from_file` から `target` への Markdown の相対リンク。どちらもプロジェクトルートからの相対パス
+fn relative_link(project_root: &Path, from_file: &Path, target: &Path) -> String {
+    // `--output-dir` で出力先が絶対パスになった場合は、どちらも絶対パスにして比較する
+    let (from_file, target) = if from_file.is_absolute() || target.is_absolute() {
+        let absolute = |path: &Path| {
+            let path = project_root.join(path);
+            std::path::absolute(&path).unwrap_or(path)
+        };
+        (absolute(from_file), absolute(target))
+    } else {
+        (from_file.to_path_buf(), target.to_path_buf())
+    };
+
+    let components = |path: &Path| -> Vec<String> {
+        path.components()
+            .filter(|c| !matches!(c, std::path::Component::CurDir))
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect()
+    };
+    let from_dir = components(from_file.parent().unwrap_or(Path::new("")));
+    let target = components(&target);
+
+    let common = from_dir
+        .iter()
+        .zip(&target)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut parts = vec!["..".to_string(); from_dir.len() - common];
+    parts.extend_from_slice(&target[common..]);
+    parts.join("/")
+}
+
+fn generate_table_of_contents(
+    project_root: &Path,
+    module_files: &[ModuleFile],
+    public_api: Option<&PublicApi>,
+    settings: &GeneratorSettings,
+) -> Result<(Block, Vec<Block>, TocItems)> {
+    let head = Block::optional(
+        "Table of Contents",
+        Priority::TopLevelItem,
+        "## Table of Contents\n\n".to_string(),
+    );
 
     let mut toc_items = Vec::new();
 
-    for entry in WalkDir::new(project_root.join("src"))
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
-    {
-        let relative_path = entry.path().strip_prefix(project_root)?;
-        collect_public_items_for_toc(&mut toc_items, entry.path(), relative_path)?;
+    for module_file in documented_module_files(module_files, settings) {
+        let api = public_api.filter(|_| module_file.kind == TargetKind::Lib);
+        collect_public_items_for_toc(&mut toc_items, project_root, module_file, api, settings);
     }
 
-    for (file_path, items) in &toc_items {
+    let mut blocks = Vec::new();
+    for (module_file, (file_path, items)) in
+        documented_module_files(module_files, settings).zip(&toc_items)
+    {
         if !items.is_empty() {
+            let mut content = String::new();
             content.push_str(&format!("### {}\n\n", file_path.display()));
+            // モジュールの `//!` の最初の段落を概要として添える
+            if let Some(summary) = doc_summary(
+                &module_file.syntax.attrs,
+                module_source_dir(project_root, module_file).as_deref(),
+            ) {
+                content.push_str(&format!("{}\n\n", summary));
+            }
             for item in items {
                 content.push_str(&format!("- {}\n", item));
             }
             content.push('\n');
+
+            let priority = if module_file.module_path.is_empty() {
+                Priority::TopLevelItem
+            } else {
+                Priority::DocumentedItem(module_file.module_path.len())
+            };
+            blocks.push(Block::optional(
+                format!("Table of Contents: {}", file_path.display()),
+                priority,
+                content,
+            ));
         }
     }
 
-    Ok((content, toc_items))
+    Ok((head, blocks, toc_items))
+}
+
+/// llms.txt に出力するサンプルの一覧
+fn format_examples_list(project_root: &Path, output: &Path, examples: &[Example]) -> String {
+    let mut content = String::new();
+    content.push_str("## Examples\n\n");
+    for example in examples {
+        let mut line = format!(
+            "- [{}]({})",
+            example.name,
+            relative_link(project_root, output, &example.relative_path)
+        );
+        if let Some(summary) = &example.summary {
+            line.push_str(&format!(": {}", summary));
+        }
+        content.push_str(&format!("{}\n", line));
+    }
+    content.push('\n');
+    content
+}
+
+/// llms-full.txt に出力するサンプルの docs とコード
+fn format_example(example: &Example, max_lines: Option<usize>) -> String {
+    let mut content = String::new();
+    content.push_str(&format!("### {}\n\n", example.name));
+    content.push_str(&format!(
+        "*Source: `{}`* (run with `cargo run --example {}`)\n\n",
+        to_link_path(&example.relative_path),
+        example.name
+    ));
+    if let Some(docs) = &example.docs {
+        content.push_str(&adjust_markdown_heading_levels(docs, 3));
+        content.push_str("\n\n");
+    }
+
+    let (source, omitted) = truncate_source(&example.source, max_lines.unwrap_or(usize::MAX));
+    content.push_str(&format!("```rust\n{}\n```\n\n", source));
+    if let Some(omitted) = omitted {
+        content.push_str(&format!(
+            "*{} more lines omitted; see `{}`.*\n\n",
+            omitted,
+            to_link_path(&example.relative_path)
+        ));
+    }
+    content
+}
+
+/// docs にコードブロックのあるモジュールとアイテム（出力順）
+fn collect_doc_examples(sections: &[ModuleSection]) -> Vec<(String, Vec<DocExample>)> {
+    let mut collected = Vec::new();
+    // llms-full.txt に出力されないモジュールは含めない
+    for section in sections.iter().filter(|s| !s.content.is_empty()) {
+        let module_label = module_label(&section.module_path, section.target.as_deref());
+        let docs = std::iter::once((module_label, section.docs.as_deref())).chain(
+            section
+                .items
+                .iter()
+                .map(|item| (item.path.clone(), item.docs.as_deref())),
+        );
+        for (label, docs) in docs {
+            let examples = docs.map(extract_doc_examples).unwrap_or_default();
+            if !examples.is_empty() {
+                collected.push((label, examples));
+            }
+        }
+    }
+    collected
+}
+
+/// モジュールまたはアイテムの docs のコードブロックを、テストとしての扱いの注記とともに出力する
+fn format_doc_examples(heading: &str, label: &str, examples: &[DocExample]) -> String {
+    let mut content = format!("{} {}\n\n", heading, label);
+    for example in examples {
+        for note in example.notes() {
+            content.push_str(&format!("*{}*\n\n", note));
+        }
+        content.push_str(&format!("```rust\n{}\n```\n\n", example.code));
+    }
+    content
+}
+
+fn format_skipped_files_section(skipped: &[SkippedFile]) -> String {
+    let mut content = String::new();
+    content.push_str("## Skipped Files\n\n");
+    content.push_str("These files could not be parsed and are not documented:\n\n");
+    for file in skipped {
+        content.push_str(&format!("- `{}`: {}\n", file.location(), file.message));
+    }
+    content.push('\n');
+    content
 }
 
 fn format_readme_section(project_root: &Path) -> Result<String> {
@@ -226,6 +906,18 @@ fn format_readme_section(project_root: &Path) -> Result<String> {
     }
 }
 
+/// 設定の `extra-docs` で指定されたドキュメントを README と同じ形式で出力する
+fn format_extra_doc_section(project_root: &Path, doc: &ExtraDoc) -> Result<String> {
+    let path = project_root.join(&doc.path);
+    let doc_content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read extra document {}", path.display()))?;
+    let mut content = String::new();
+    content.push_str(&format!("## {}\n\n", doc.title()));
+    content.push_str(&adjust_markdown_heading_levels(&doc_content, 2));
+    content.push_str("\n\n");
+    Ok(content)
+}
+
 fn format_cargo_toml_section(project_root: &Path) -> Result<String> {
     if project_root.join("Cargo.toml").exists() {
         let cargo_content = fs::read_to_string(project_root.join("Cargo.toml"))?;
@@ -240,47 +932,238 @@ fn format_cargo_toml_section(project_root: &Path) -> Result<String> {
     }
 }
 
-fn format_complete_api_docs(project_root: &Path, _toc_items: &TocItems) -> Result<String> {
-    let mut content = String::new();
-
-    // llms-full.txtの場合はREADME.mdの後にセパレータを追加
-    if project_root.join("README.md").exists() {
-        content.push_str("---\n\n");
+fn format_complete_api_docs(
+    blocks: &mut Vec<Block>,
+    sections: &[ModuleSection],
+    _toc_items: &TocItems,
+) -> Result<()> {
+    for section in sections {
+        let (head, children) = module_section_blocks(section);
+        push_block_group(blocks, head, children);
     }
 
-    // 完全なAPIドキュメントを生成
-    for entry in WalkDir::new(project_root.join("src"))
+    Ok(())
+}
+
+/// モジュールセクションを見出し・概要のブロックとアイテムごとのブロックに分割する。
+/// クレートルートを優先し、深いモジュールや docs のないアイテムほど優先度を下げる
+fn module_section_blocks(section: &ModuleSection) -> (Block, Vec<Block>) {
+    let depth = section.module_path.len();
+    let (head, entries) = split_entries(&section.content);
+
+    let label = module_label(&section.module_path, section.target.as_deref());
+    let head_priority = if depth == 0 {
+        Priority::CrateDocs
+    } else if section.docs.is_some() {
+        Priority::DocumentedItem(depth)
+    } else {
+        Priority::ModuleItem(depth)
+    };
+    let head = Block::optional(label, head_priority, head.to_string());
+
+    let children = entries
         .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
-    {
-        let relative_path = entry.path().strip_prefix(project_root)?;
-        extract_complete_api_docs(&mut content, entry.path(), relative_path)?;
+        .map(|entry| {
+            let priority = if depth == 0 {
+                Priority::TopLevelItem
+            } else if entry_has_docs(entry) {
+                Priority::DocumentedItem(depth)
+            } else {
+                Priority::ModuleItem(depth)
+            };
+            Block::optional(entry_title(entry), priority, entry.to_string())
+        })
+        .collect();
+
+    (head, children)
+}
+
+/// 完全なAPIドキュメントをターゲットごとにモジュールツリーの順で生成する
+fn collect_module_sections(
+    project_root: &Path,
+    module_files: &[ModuleFile],
+    public_api: Option<&PublicApi>,
+    settings: &GeneratorSettings,
+) -> Vec<ModuleSection> {
+    collect_target_sections(project_root, module_files, public_api, settings)
+        .into_iter()
+        .flat_map(|target| target.sections)
+        .collect()
+}
+
+/// ターゲットごとのモジュールセクション。
+/// モジュールファイルはクレートルートから次のターゲットが始まる
+fn collect_target_sections(
+    project_root: &Path,
+    module_files: &[ModuleFile],
+    public_api: Option<&PublicApi>,
+    settings: &GeneratorSettings,
+) -> Vec<TargetSections> {
+    let mut targets = Vec::new();
+    for target_files in module_files.chunk_by(|_, next| !next.module_path.is_empty()) {
+        let impls = ImplIndex::build(project_root, target_files);
+
+        // バイナリターゲットはバイナリ名で区別する（チャンク id は `bin.cli`、見出しは `bin cli`）
+        let bin_name = (target_files[0].kind != TargetKind::Lib)
+            .then(|| bin_target_name(&target_files[0].relative_path));
+        let label = bin_name.as_ref().map(|name| format!("bin {}", name));
+
+        let mut sections = Vec::new();
+        for module_file in documented_module_files(target_files, settings) {
+            let api = public_api.filter(|_| module_file.kind == TargetKind::Lib);
+            extract_complete_api_docs(
+                &mut sections,
+                project_root,
+                module_file,
+                label.as_deref(),
+                api,
+                &impls,
+                settings,
+            );
+        }
+        sections.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+
+        let prefix = bin_name.map(|name| format!("bin.{}", name));
+        targets.push(TargetSections { prefix, sections });
     }
+    targets
+}
 
-    Ok(content)
+/// バイナリターゲットの名前。`src/bin/tool/main.rs` はディレクトリ名を使う
+fn bin_target_name(root: &Path) -> String {
+    let dir = root
+        .parent()
+        .filter(|dir| root.ends_with("main.rs") && !dir.ends_with("src"));
+    dir.unwrap_or(root)
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
 }
 
-fn collect_public_items_for_toc(
-    toc_items: &mut TocItems,
-    file_path: &Path,
-    relative_path: &Path,
+/// llms-full.txt の代わりに `llms-full/` 以下にチャンクファイルと manifest を出力する
+fn generate_chunks(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    sources: &CrateSources,
+    mode: ChunkMode,
+    settings: &GeneratorSettings,
+    out: &mut OutputWriter,
 ) -> Result<()> {
-    let source = fs::read_to_string(file_path)?;
-    let syntax_tree = syn::parse_file(&source)?;
+    let CrateSources {
+        module_files,
+        skipped,
+    } = sources;
+    let public_api = build_public_api(module_files);
+    let targets =
+        collect_target_sections(project_root, module_files, public_api.as_ref(), settings);
+
+    let project_name = project_name(project_root, project_info);
+    let chunks = build_chunks(project_name, &targets, mode, settings.chunk_tokens);
+
+    // 今回生成されないチャンクが前回の出力から残らないように削除する
+    let chunk_dir = project_root.join(chunk_dir(&settings.outputs));
+    if chunk_dir.is_dir() {
+        let mut stale_files = Vec::new();
+        for entry in fs::read_dir(&chunk_dir)? {
+            let path = entry?.path();
+            let is_chunk = path.extension().is_some_and(|ext| ext == "md");
+            let generated = chunks.iter().any(|chunk| {
+                path.file_name()
+                    .is_some_and(|name| name == chunk.entry.file.as_str())
+            });
+            if is_chunk && !generated {
+                stale_files.push(path);
+            }
+        }
+        // 出力が環境によらず同じ順になるようにする
+        stale_files.sort();
+        for path in stale_files {
+            out.remove(&path)?;
+        }
+    }
+    out.create_dir(&chunk_dir)?;
 
-    let mut items = Vec::new();
-    let mut visitor = TocVisitor {
-        items: &mut items,
-        current_mod: Vec::new(),
-    };
-    visitor.visit_file(&syntax_tree);
+    let mut entries = Vec::new();
+    for chunk in chunks {
+        out.write(&chunk_dir.join(&chunk.entry.file), &chunk.content)?;
+        entries.push(chunk.entry);
+    }
 
-    toc_items.push((relative_path.to_path_buf(), items));
+    let manifest = ChunkManifest {
+        name: project_name.to_string(),
+        chunks: entries,
+        skipped_files: skipped_file_models(skipped),
+    };
+    let mut json = serde_json::to_string_pretty(&manifest)?;
+    json.push('\n');
+    out.write(&chunk_dir.join(MANIFEST_FILE), &json)?;
     Ok(())
 }
 
+/// チャンクファイルの出力先。llms-full.txt と同じディレクトリに置く
+fn chunk_dir(outputs: &OutputPaths) -> PathBuf {
+    outputs.full.with_file_name(CHUNK_DIR)
+}
+
+/// llms.txt からリンクする完全なAPIドキュメントのパス
+fn full_docs_path(outputs: &OutputPaths, settings: &GeneratorSettings) -> PathBuf {
+    match settings.chunks {
+        Some(_) => chunk_dir(outputs).join(MANIFEST_FILE),
+        None => outputs.full.clone(),
+    }
+}
+
+fn crate_root_summary(project_root: &Path, module_files: &[ModuleFile]) -> Option<String> {
+    let roots = module_files.iter().filter(|f| f.module_path.is_empty());
+    let root = roots
+        .clone()
+        .find(|f| f.kind == TargetKind::Lib)
+        .or_else(|| roots.clone().next())?;
+    doc_summary(
+        &root.syntax.attrs,
+        module_source_dir(project_root, root).as_deref(),
+    )
+}
+
+/// `include_str!` の基準となるモジュールファイルのディレクトリ
+fn module_source_dir(project_root: &Path, module_file: &ModuleFile) -> Option<PathBuf> {
+    project_root
+        .join(&module_file.relative_path)
+        .parent()
+        .map(Path::to_path_buf)
+}
+
+/// `#[doc(hidden)]` なモジュールと `include` / `exclude` で除外したファイルを除いたモジュールファイルを列挙する
+fn documented_module_files<'a>(
+    module_files: &'a [ModuleFile],
+    settings: &'a GeneratorSettings,
+) -> impl Iterator<Item = &'a ModuleFile> {
+    module_files
+        .iter()
+        .filter(|f| settings.include_hidden || !f.doc_hidden)
+        .filter(|f| settings.files.matches(&f.relative_path))
+}
+
+fn collect_public_items_for_toc(
+    toc_items: &mut TocItems,
+    project_root: &Path,
+    module_file: &ModuleFile,
+    public_api: Option<&PublicApi>,
+    settings: &GeneratorSettings,
+) {
+    let mut items = Vec::new();
+    let mut visitor = TocVisitor::new(&mut items);
+    visitor.current_mod = module_file.module_path.clone();
+    visitor.public_api = public_api;
+    visitor.visibility = settings.visibility;
+    visitor.include_hidden = settings.include_hidden;
+    visitor.source_dir = module_source_dir(project_root, module_file);
+    visitor.visit_file(&module_file.syntax);
+
+    toc_items.push((module_file.relative_path.clone(), items));
+}
+
 /// Markdown見出しレベルを調整する関数
 /// base_level: 基準となる見出しレベル（例: 2 なら ## が基準）
 fn adjust_markdown_heading_levels(content: &str, base_level: usize) -> String {
@@ -308,23 +1191,47 @@ fn adjust_markdown_heading_levels(content: &str, base_level: usize) -> String {
 }
 
 fn extract_complete_api_docs(
-    content: &mut String,
-    file_path: &Path,
-    relative_path: &Path,
-) -> Result<()> {
-    let source = fs::read_to_string(file_path)?;
-    let syntax_tree = syn::parse_file(&source)?;
-
-    content.push_str(&format!("## {}\n\n", relative_path.display()));
+    sections: &mut Vec<ModuleSection>,
+    project_root: &Path,
+    module_file: &ModuleFile,
+    target: Option<&str>,
+    public_api: Option<&PublicApi>,
+    impls: &ImplIndex,
+    settings: &GeneratorSettings,
+) {
+    // 公開APIの出力では到達できないモジュールの概要は出さない
+    let documented = module_file.module_path.is_empty()
+        || settings.visibility != VisibilityLevel::Public
+        || public_api.is_none_or(|api| api.is_module_reachable(&module_file.module_path));
 
-    let mut visitor = CompleteDocsVisitor {
+    let mut content = String::new();
+    let mut visitor = CompleteDocsVisitor::new(&mut content);
+    visitor.current_mod = module_file.module_path.clone();
+    visitor.public_api = public_api;
+    visitor.visibility = settings.visibility;
+    visitor.include_hidden = settings.include_hidden;
+    visitor.impls = Some(impls);
+    visitor.source_dir = module_source_dir(project_root, module_file);
+    visitor.source_file = module_file.relative_path.clone();
+    visitor.target = target.map(str::to_string);
+    visitor.render_module(
+        &module_file.syntax.attrs,
+        &module_file.syntax.items,
+        documented,
+    );
+    let submodules = std::mem::take(&mut visitor.submodules);
+    let docs = visitor.module_docs.take();
+    let items = std::mem::take(&mut visitor.items);
+
+    sections.push(ModuleSection {
+        module_path: module_file.module_path.clone(),
+        target: target.map(str::to_string),
+        source_file: module_file.relative_path.clone(),
+        docs,
         content,
-        current_mod: Vec::new(),
-    };
-    visitor.visit_file(&syntax_tree);
-
-    content.push('\n');
-    Ok(())
+        items,
+    });
+    sections.extend(submodules);
 }
 
 #[cfg(test)]
@@ -345,15 +1252,19 @@ mod tests {
             keywords: None,
             dependencies: None,
             features: None,
+            workspace: None,
+            targets: Default::default(),
+            config: Default::default(),
         };
 
-        let result = format_project_info(&project_info).unwrap();
+        let timestamp = DateTime::from_timestamp(1_700_000_000, 0);
+        let result = format_project_info(&project_info, true, timestamp).unwrap();
 
         assert!(result.contains("> A test project"));
         assert!(result.contains("**Version:** 1.0.0"));
         assert!(result.contains("**Authors:** Test Author <test@example.com>"));
         assert!(result.contains("**License:** MIT"));
-        assert!(result.contains("Generated:"));
+        assert!(result.contains("Generated: 2023-11-14 22:13:20 UTC"));
         assert!(result.contains("Created by: [cargo-llms-txt]"));
     }
 
@@ -383,9 +1294,12 @@ mod tests {
             keywords: Some(vec!["async".to_string(), "web".to_string()]),
             dependencies: Some(deps),
             features: None,
+            workspace: None,
+            targets: Default::default(),
+            config: Default::default(),
         };
 
-        let result = format_project_info(&project_info).unwrap();
+        let result = format_project_info(&project_info, true, Some(Utc::now())).unwrap();
 
         assert!(result.contains("**Keywords:** async, web"));
         assert!(result.contains("**Dependencies:**"));
@@ -406,9 +1320,12 @@ mod tests {
             keywords: None,
             dependencies: None,
             features: None,
+            workspace: None,
+            targets: Default::default(),
+            config: Default::default(),
         };
 
-        let result = format_project_info(&project_info).unwrap();
+        let result = format_project_info(&project_info, true, Some(Utc::now())).unwrap();
 
         // Should only contain generated timestamp and credit
         assert!(result.contains("Generated:"));
@@ -418,6 +1335,36 @@ mod tests {
         assert!(!result.contains(">")); // No description
     }
 
+    #[test]
+    fn test_format_project_info_without_timestamp() {
+        let project_info = ProjectInfo {
+            name: Some("minimal_project".to_string()),
+            ..Default::default()
+        };
+
+        let result = format_project_info(&project_info, true, None).unwrap();
+
+        assert!(!result.contains("Generated:"));
+        assert!(result.starts_with("Created by: [cargo-llms-txt]"));
+    }
+
+    #[test]
+    fn test_resolve_timestamp() {
+        let pinned = DateTime::from_timestamp(1_700_000_000, 0);
+
+        assert_eq!(resolve_timestamp(true, Some(1), Some("2")).unwrap(), None);
+        assert_eq!(
+            resolve_timestamp(false, Some(1_700_000_000), Some("2")).unwrap(),
+            pinned
+        );
+        assert_eq!(
+            resolve_timestamp(false, None, Some("1700000000\n")).unwrap(),
+            pinned
+        );
+        assert!(resolve_timestamp(false, None, None).unwrap().is_some());
+        assert!(resolve_timestamp(false, None, Some("yesterday")).is_err());
+    }
+
     #[test]
     fn test_format_core_documentation_section() {
         // Create a temporary directory structure for testing
@@ -429,7 +1376,7 @@ mod tests {
         // Create Cargo.toml
         std::fs::write(temp_dir.join("Cargo.toml"), "[package]\nname = \"test\"").unwrap();
 
-        let result = format_core_documentation_section(&temp_dir);
+        let result = format_core_documentation_section(&temp_dir, &GeneratorSettings::default());
 
         assert!(result.contains("## Core Documentation"));
         assert!(result.contains("- [Complete API Documentation](llms-full.txt)"));
@@ -446,7 +1393,7 @@ mod tests {
         let temp_dir = std::env::temp_dir().join("cargo_llms_txt_test_core_docs_minimal");
         std::fs::create_dir_all(&temp_dir).unwrap();
 
-        let result = format_core_documentation_section(&temp_dir);
+        let result = format_core_documentation_section(&temp_dir, &GeneratorSettings::default());
 
         assert!(result.contains("## Core Documentation"));
         assert!(result.contains("- [Complete API Documentation](llms-full.txt)"));
@@ -457,6 +1404,93 @@ mod tests {
         std::fs::remove_dir_all(&temp_dir).unwrap();
     }
 
+    #[test]
+    fn test_format_workspace_members_section() {
+        use crate::project_info::{WorkspaceInfo, WorkspaceMember};
+
+        let member_info = ProjectInfo {
+            name: Some("member_crate".to_string()),
+            version: Some("0.1.0".to_string()),
+            description: Some("A member crate".to_string()),
+            ..Default::default()
+        };
+        let project_info = ProjectInfo {
+            workspace: Some(WorkspaceInfo {
+                members: vec![WorkspaceMember {
+                    path: PathBuf::from("crates").join("member"),
+                    info: member_info,
+                }],
+            }),
+            ..Default::default()
+        };
+
+        let result = format_workspace_members_section(
+            Path::new("."),
+            &project_info,
+            &GeneratorSettings::default(),
+        );
+
+        assert!(result.contains("## Workspace Members"));
+        assert!(result.contains("- **member_crate** (0.1.0): A member crate"));
+        assert!(result.contains("[llms.txt](crates/member/llms.txt)"));
+        assert!(result.contains("[llms-full.txt](crates/member/llms-full.txt)"));
+
+        // ワークスペースでなければ何も出力しない
+        assert!(format_workspace_members_section(
+            Path::new("."),
+            &ProjectInfo::default(),
+            &GeneratorSettings::default()
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_relative_link() {
+        let root = Path::new("project");
+        assert_eq!(
+            relative_link(root, Path::new("llms.txt"), Path::new("README.md")),
+            "README.md"
+        );
+        assert_eq!(
+            relative_link(root, Path::new("docs/llms.txt"), Path::new("./README.md")),
+            "../README.md"
+        );
+        assert_eq!(
+            relative_link(
+                root,
+                Path::new("docs/llms.txt"),
+                Path::new("docs/llms-full/manifest.json")
+            ),
+            "llms-full/manifest.json"
+        );
+
+        // 出力先が絶対パスの場合はプロジェクトルートを基準に解決する
+        let out = std::path::absolute("out/llms.txt").unwrap();
+        assert_eq!(
+            relative_link(root, &out, Path::new("README.md")),
+            "../project/README.md"
+        );
+    }
+
+    #[test]
+    fn test_collect_target_sections_without_lib() {
+        // lib.rs がなくてもバイナリのクレートルートはバイナリとして扱う
+        let bin = |path: &str| ModuleFile {
+            relative_path: PathBuf::from(path),
+            module_path: Vec::new(),
+            kind: TargetKind::Bin,
+            doc_hidden: false,
+            syntax: syn::parse_str("pub fn run() {}").unwrap(),
+        };
+        let files = [bin("src/main.rs"), bin("src/bin/tool.rs")];
+        let targets =
+            collect_target_sections(Path::new("."), &files, None, &GeneratorSettings::default());
+
+        let prefixes: Vec<_> = targets.iter().map(|t| t.prefix.as_deref()).collect();
+        assert_eq!(prefixes, vec![Some("bin.main"), Some("bin.tool")]);
+        assert_eq!(targets[0].sections[0].target.as_deref(), Some("bin main"));
+    }
+
     #[test]
     fn test_adjust_markdown_heading_levels() {
         let input = r#"# Main Title
@@ -0,0 +1,332 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::modules::ModuleFile;
+use crate::public_api::CrateScopes;
+
+/// 型に紐づけられた impl ブロック
+pub struct ImplBlock {
+    /// impl ブロックが書かれているモジュール
+    pub module_path: Vec<String>,
+    /// `include_str!` を解決する基準ディレクトリ
+    pub source_dir: Option<PathBuf>,
+    pub item: syn::ItemImpl,
+}
+
+/// クレート内の型（struct / enum / union）ごとに impl ブロックを集めた索引
+#[derive(Default)]
+pub struct ImplIndex {
+    scopes: CrateScopes,
+    /// クレート内で定義されている型の定義位置
+    types: HashSet<Vec<String>>,
+    /// 型の定義位置 → impl ブロック（出現順）
+    impls: HashMap<Vec<String>, Vec<ImplBlock>>,
+    /// `impl<T: Bound> Trait for T` のようなブランケット実装
+    blanket: Vec<ImplBlock>,
+}
+
+impl ImplIndex {
+    /// ターゲットのモジュールファイルから impl ブロックを収集する
+    pub fn build<'a>(
+        project_root: &Path,
+        files: impl IntoIterator<Item = &'a ModuleFile> + Clone,
+    ) -> Self {
+        let mut index = ImplIndex {
+            scopes: CrateScopes::build(files.clone()),
+            ..Default::default()
+        };
+
+        let mut blocks = Vec::new();
+        for file in files {
+            let source_dir = project_root
+                .join(&file.relative_path)
+                .parent()
+                .map(Path::to_path_buf);
+            index.collect_items(
+                &file.syntax.items,
+                &file.module_path,
+                &source_dir,
+                &mut blocks,
+            );
+        }
+
+        // 型の定義をすべて集めてから impl の対象を解決する
+        for block in blocks {
+            if let Some(type_path) =
+                index.resolve_self_type(&block.module_path, &block.item.self_ty)
+            {
+                index.impls.entry(type_path).or_default().push(block);
+            } else if blanket_param(&block.item).is_some() {
+                index.blanket.push(block);
+            }
+        }
+
+        index
+    }
+
+    fn collect_items(
+        &mut self,
+        items: &[syn::Item],
+        module_path: &[String],
+        source_dir: &Option<PathBuf>,
+        blocks: &mut Vec<ImplBlock>,
+    ) {
+        for item in items {
+            let ident = match item {
+                syn::Item::Struct(i) => &i.ident,
+                syn::Item::Enum(i) => &i.ident,
+                syn::Item::Union(i) => &i.ident,
+                syn::Item::Impl(item_impl) => {
+                    blocks.push(ImplBlock {
+                        module_path: module_path.to_vec(),
+                        source_dir: source_dir.clone(),
+                        item: item_impl.clone(),
+                    });
+                    continue;
+                }
+                syn::Item::Mod(item_mod) => {
+                    if let Some((_, inline_items)) = &item_mod.content {
+                        let mut inline_path = module_path.to_vec();
+                        inline_path.push(item_mod.ident.to_string());
+                        self.collect_items(inline_items, &inline_path, source_dir, blocks);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let mut type_path = module_path.to_vec();
+            type_path.push(ident.to_string());
+            self.types.insert(type_path);
+        }
+    }
+
+    /// impl の対象型をクレート内の型の定義位置に解決する。
+    /// クレート外の型やジェネリクスのパラメータの場合は None を返す
+    pub fn resolve_self_type(
+        &self,
+        module_path: &[String],
+        self_ty: &syn::Type,
+    ) -> Option<Vec<String>> {
+        let syn::Type::Path(type_path) = self_ty else {
+            return None;
+        };
+        if type_path.qself.is_some() || type_path.path.leading_colon.is_some() {
+            return None;
+        }
+
+        let segments: Vec<String> = type_path
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect();
+        self.scopes
+            .resolve(module_path, &segments)
+            .filter(|resolved| self.types.contains(resolved))
+    }
+
+    /// 型に対する impl ブロックを出現順に返す
+    pub fn impls_for(&self, type_path: &[String]) -> &[ImplBlock] {
+        self.impls
+            .get(type_path)
+            .map(|blocks| blocks.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// 型が実装しているトレイト名（最後のセグメント）から、その型に適用されるブランケット実装を返す。
+    /// ブランケット実装で得られるトレイトも境界の判定に含める
+    pub fn blanket_impls_for(&self, implemented: &HashSet<String>) -> Vec<&ImplBlock> {
+        let mut implemented = implemented.clone();
+        let mut applied = vec![false; self.blanket.len()];
+        loop {
+            let mut changed = false;
+            for (block, applied) in self.blanket.iter().zip(applied.iter_mut()) {
+                if *applied
+                    || !blanket_bounds(&block.item)
+                        .iter()
+                        .all(|bound| implemented.contains(bound))
+                {
+                    continue;
+                }
+                *applied = true;
+                changed = true;
+                if let Some((_, trait_path, _)) = &block.item.trait_ {
+                    if let Some(segment) = trait_path.segments.last() {
+                        implemented.insert(segment.ident.to_string());
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        self.blanket
+            .iter()
+            .zip(applied)
+            .filter_map(|(block, applied)| applied.then_some(block))
+            .collect()
+    }
+}
+
+/// ブランケット実装の対象になっているジェネリクスのパラメータを返す
+fn blanket_param(item: &syn::ItemImpl) -> Option<&syn::Ident> {
+    item.trait_.as_ref()?;
+    let syn::Type::Path(type_path) = &*item.self_ty else {
+        return None;
+    };
+    let ident = type_path.path.get_ident()?;
+    item.generics
+        .type_params()
+        .any(|param| &param.ident == ident)
+        .then_some(ident)
+}
+
+/// ブランケット実装のパラメータに課されたトレイト境界（`?Sized` を除く）
+fn blanket_bounds(item: &syn::ItemImpl) -> Vec<String> {
+    let Some(param) = blanket_param(item) else {
+        return Vec::new();
+    };
+
+    let mut bounds: Vec<&syn::TypeParamBound> = item
+        .generics
+        .type_params()
+        .filter(|p| &p.ident == param)
+        .flat_map(|p| &p.bounds)
+        .collect();
+    if let Some(where_clause) = &item.generics.where_clause {
+        for predicate in &where_clause.predicates {
+            if let syn::WherePredicate::Type(predicate_type) = predicate {
+                let is_param = matches!(
+                    &predicate_type.bounded_ty,
+                    syn::Type::Path(p) if p.path.is_ident(param)
+                );
+                if is_param {
+                    bounds.extend(&predicate_type.bounds);
+                }
+            }
+        }
+    }
+
+    bounds
+        .into_iter()
+        .filter_map(|bound| match bound {
+            syn::TypeParamBound::Trait(trait_bound)
+                if matches!(trait_bound.modifier, syn::TraitBoundModifier::None) =>
+            {
+                trait_bound
+                    .path
+                    .segments
+                    .last()
+                    .map(|segment| segment.ident.to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::TargetKind;
+
+    fn module_file(module_path: &[&str], source: &str) -> ModuleFile {
+        ModuleFile {
+            relative_path: Default::default(),
+            module_path: module_path.iter().map(|s| s.to_string()).collect(),
+            kind: TargetKind::Lib,
+            doc_hidden: false,
+            syntax: syn::parse_str(source).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_impl_index_resolves_self_types() {
+        let files = [
+            module_file(
+                &[],
+                r#"
+                mod model;
+                mod ops;
+                pub use model::Widget;
+
+                impl<T> Local for T {}
+                impl std::fmt::Display for String {}
+                "#,
+            ),
+            module_file(
+                &["model"],
+                r#"
+                pub struct Widget;
+                impl Widget { pub fn new() -> Self { Widget } }
+
+                pub mod nested {
+                    pub enum Kind { A }
+                    impl Kind { pub fn is_a(&self) -> bool { true } }
+                }
+                "#,
+            ),
+            module_file(
+                &["ops"],
+                r#"
+                use crate::model::Widget;
+                impl Clone for Widget { fn clone(&self) -> Self { Widget } }
+                impl super::model::nested::Kind { pub fn name(&self) -> &str { "" } }
+                "#,
+            ),
+        ];
+        let index = ImplIndex::build(Path::new(""), &files);
+
+        let path = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(index.impls_for(&path(&["model", "Widget"])).len(), 2);
+        assert_eq!(
+            index.impls_for(&path(&["model", "nested", "Kind"])).len(),
+            2
+        );
+
+        // ジェネリクスのパラメータやクレート外の型は解決されない
+        for item in &files[0].syntax.items {
+            if let syn::Item::Impl(item_impl) = item {
+                assert_eq!(index.resolve_self_type(&[], &item_impl.self_ty), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_blanket_impls_for() {
+        let files = [module_file(
+            &[],
+            r#"
+            pub trait Describe {}
+            pub trait Shout {}
+            pub trait Anything {}
+            impl<T: std::fmt::Display + ?Sized> Describe for T {}
+            impl<T> Shout for T where T: Describe + Clone {}
+            impl<T: ?Sized> Anything for T {}
+            impl<T> Describe for Vec<T> {}
+            "#,
+        )];
+        let index = ImplIndex::build(Path::new(""), &files);
+
+        let traits_of = |implemented: &[&str]| {
+            let implemented = implemented.iter().map(|s| s.to_string()).collect();
+            index
+                .blanket_impls_for(&implemented)
+                .iter()
+                .map(|block| {
+                    let (_, path, _) = block.item.trait_.as_ref().unwrap();
+                    path.get_ident().unwrap().to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(traits_of(&[]), vec!["Anything"]);
+        assert_eq!(traits_of(&["Display"]), vec!["Describe", "Anything"]);
+        // Describe はブランケット実装から得られる
+        assert_eq!(
+            traits_of(&["Display", "Clone"]),
+            vec!["Describe", "Shout", "Anything"]
+        );
+    }
+}
@@ -1,13 +1,32 @@
 use anyhow::Result;
 use clap::Parser;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+mod budget;
+mod chunks;
+mod config;
+mod doctests;
+mod examples;
 mod generator;
+mod impls;
+mod model;
+mod modules;
+mod output;
 mod project_info;
+mod public_api;
 mod visitors;
 
-use generator::{generate_llms_full_txt, generate_llms_txt};
-use project_info::get_project_info;
+use chrono::{DateTime, Utc};
+use chunks::ChunkMode;
+use config::{Config, FileFilter};
+use generator::{
+    generate_doc_examples, generate_llms_full_txt, generate_llms_json, generate_llms_txt,
+    generate_workspace_index, resolve_timestamp, GeneratorSettings,
+};
+use modules::discover_module_files;
+use output::{OutputMode, OutputWriter};
+use project_info::{get_project_info, ProjectInfo};
+use visitors::VisibilityLevel;
 
 #[derive(Parser)]
 #[command(name = "cargo-llms-txt")]
@@ -15,6 +34,169 @@ use project_info::get_project_info;
 struct Args {
     #[arg(short, long, default_value = ".")]
     path: PathBuf,
+
+    /// Visibility level of items to document (public, crate or all) [default: public]
+    #[arg(long, value_enum)]
+    visibility: Option<VisibilityLevel>,
+
+    /// Also document items marked with #[doc(hidden)]
+    #[arg(long, overrides_with = "no_include_hidden")]
+    include_hidden: bool,
+
+    /// Skip items marked with #[doc(hidden)] even when the config enables include-hidden
+    #[arg(long, overrides_with = "include_hidden")]
+    no_include_hidden: bool,
+
+    /// Trim each generated file to about this many tokens, dropping lower-priority sections first
+    #[arg(long, value_name = "N")]
+    max_tokens: Option<usize>,
+
+    /// Write llms-full/ chunk files (one per module, or size-bounded) and a manifest instead of llms-full.txt
+    #[arg(long, value_enum)]
+    chunks: Option<ChunkMode>,
+
+    /// Approximate token limit per chunk with --chunks size
+    #[arg(long, value_name = "N", default_value_t = 4000)]
+    chunk_tokens: usize,
+
+    /// Output format (markdown writes llms.txt and llms-full.txt, json writes llms.json)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+    format: OutputFormat,
+
+    /// Omit the Generated: timestamp line
+    #[arg(long, conflicts_with = "timestamp")]
+    no_timestamp: bool,
+
+    /// Use this Unix time for the Generated: line instead of the current time (defaults to SOURCE_DATE_EPOCH when set)
+    #[arg(long, value_name = "UNIX_SECONDS")]
+    timestamp: Option<i64>,
+
+    /// Check that the generated files are up to date instead of writing them (ignores the Generated: line)
+    #[arg(long)]
+    check: bool,
+
+    /// Write the generated files under this directory instead of the project directory
+    #[arg(long, value_name = "DIR")]
+    output_dir: Option<PathBuf>,
+
+    /// Print the generated files to stdout instead of writing them
+    #[arg(long, conflicts_with_all = ["check", "output_dir", "chunks"])]
+    stdout: bool,
+
+    /// File name for llms.txt, relative to the output directory
+    #[arg(long, value_name = "PATH")]
+    llms_file: Option<PathBuf>,
+
+    /// File name for llms-full.txt, relative to the output directory
+    #[arg(long, value_name = "PATH")]
+    full_file: Option<PathBuf>,
+
+    /// File name for llms.json, relative to the output directory
+    #[arg(long, value_name = "PATH")]
+    json_file: Option<PathBuf>,
+
+    /// Also write the code examples from doc comments to this file, relative to the output directory
+    #[arg(long, value_name = "PATH")]
+    examples_file: Option<PathBuf>,
+
+    /// Generate only llms.txt or only llms-full.txt
+    #[arg(long, value_enum)]
+    only: Option<OnlyFile>,
+
+    /// Fail on source files that cannot be parsed instead of skipping them with a warning
+    #[arg(long)]
+    strict: bool,
+
+    /// Maximum number of lines of each example included in llms-full.txt [default: 200]
+    #[arg(long, value_name = "N")]
+    example_max_lines: Option<usize>,
+}
+
+/// llms-full.txt に含めるサンプルごとの最大行数の既定値
+const DEFAULT_EXAMPLE_MAX_LINES: usize = 200;
+
+/// 出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    Markdown,
+    Json,
+}
+
+/// `--only` で生成するファイル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum OnlyFile {
+    Llms,
+    Full,
+}
+
+impl Args {
+    /// クレートの設定にコマンドラインの指定を重ねた生成設定
+    fn settings(
+        &self,
+        config: &Config,
+        timestamp: Option<DateTime<Utc>>,
+    ) -> Result<GeneratorSettings> {
+        Ok(GeneratorSettings {
+            visibility: self.visibility.or(config.visibility).unwrap_or_default(),
+            include_hidden: self
+                .include_hidden_flag()
+                .or(config.include_hidden)
+                .unwrap_or(false),
+            max_tokens: self.max_tokens,
+            chunks: self.chunks,
+            chunk_tokens: self.chunk_tokens,
+            timestamp,
+            files: FileFilter::new(&config.include, &config.exclude)?,
+            sections: config.sections.clone(),
+            outputs: config.output.paths(),
+            extra_docs: config.extra_docs.clone(),
+            strict: self.strict,
+            example_max_lines: Some(
+                self.example_max_lines
+                    .or(config.example_max_lines)
+                    .unwrap_or(DEFAULT_EXAMPLE_MAX_LINES),
+            ),
+        })
+    }
+
+    /// `--include-hidden` / `--no-include-hidden` の指定（後に指定した方が優先）
+    fn include_hidden_flag(&self) -> Option<bool> {
+        if self.include_hidden {
+            Some(true)
+        } else if self.no_include_hidden {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// 出力先に関するコマンドラインの指定をクレートの設定に反映する。
+    /// `member` はワークスペースルートからの相対パスで、`--output-dir` の下でもメンバーごとに分ける
+    fn apply_output_overrides(&self, config: &mut Config, member: &Path) -> Result<()> {
+        let output = &mut config.output;
+        if let Some(path) = &self.llms_file {
+            output.llms = Some(path.clone());
+        }
+        if let Some(path) = &self.full_file {
+            output.full = Some(path.clone());
+        }
+        if let Some(path) = &self.json_file {
+            output.json = Some(path.clone());
+        }
+        if let Some(path) = &self.examples_file {
+            output.examples = Some(path.clone());
+        }
+
+        if let Some(dir) = &self.output_dir {
+            let base = std::path::absolute(dir)?.join(member);
+            let paths = output.paths();
+            output.llms = Some(base.join(paths.llms));
+            output.full = Some(base.join(paths.full));
+            output.json = Some(base.join(paths.json));
+            output.examples = paths.examples.map(|path| base.join(path));
+        }
+        Ok(())
+    }
 }
 
 fn main() -> Result<()> {
@@ -27,16 +209,161 @@ fn main() -> Result<()> {
     let args = Args::parse_from(args);
 
     let project_root = &args.path;
+    anyhow::ensure!(
+        project_root.is_dir(),
+        "Project directory not found: {}",
+        project_root.display()
+    );
+    let timestamp = resolve_timestamp(
+        args.no_timestamp,
+        args.timestamp,
+        std::env::var("SOURCE_DATE_EPOCH").ok().as_deref(),
+    )?;
+    let settings_for = |info: &ProjectInfo| args.settings(&info.config, timestamp);
 
     // プロジェクト情報を取得
-    let project_info = get_project_info(project_root)?;
+    let mut project_info = get_project_info(project_root)?;
+    args.apply_output_overrides(&mut project_info.config, Path::new(""))?;
+    if let Some(workspace) = &mut project_info.workspace {
+        for member in &mut workspace.members {
+            args.apply_output_overrides(&mut member.info.config, &member.path)?;
+        }
+    }
+
+    let mode = if args.check {
+        OutputMode::Check
+    } else if args.stdout {
+        OutputMode::Stdout
+    } else {
+        OutputMode::Write
+    };
+    let mut out = OutputWriter::new(mode);
+    let summary = match args.format {
+        OutputFormat::Markdown => generate_markdown(
+            project_root,
+            &project_info,
+            args.only,
+            &settings_for,
+            &mut out,
+        )?,
+        OutputFormat::Json => generate_json(project_root, &project_info, &settings_for, &mut out)?,
+    };
+
+    match mode {
+        OutputMode::Write => {
+            println!("{}", summary);
+            return Ok(());
+        }
+        // 標準出力には生成した内容だけを出力する
+        OutputMode::Stdout => return Ok(()),
+        OutputMode::Check => {}
+    }
+
+    // --check では古いファイルがあれば差分を表示済みなので、終了コードで知らせる
+    if !out.stale.is_empty() {
+        eprintln!(
+            "{} generated file(s) are out of date; run `cargo llms-txt` to update them",
+            out.stale.len()
+        );
+        std::process::exit(1);
+    }
+    println!("Generated files are up to date");
+    Ok(())
+}
+
+/// llms.txt と llms-full.txt を生成し、完了メッセージを返す
+fn generate_markdown(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    only: Option<OnlyFile>,
+    settings_for: &impl Fn(&ProjectInfo) -> Result<GeneratorSettings>,
+    out: &mut OutputWriter,
+) -> Result<String> {
+    // ワークスペースの各メンバーに対して生成
+    if let Some(workspace) = &project_info.workspace {
+        for member in &workspace.members {
+            let member_root = project_root.join(&member.path);
+            let settings = settings_for(&member.info)?;
+            let sources = discover_module_files(&member_root, &member.info, settings.strict)?;
+            if only != Some(OnlyFile::Full) {
+                generate_llms_txt(&member_root, &member.info, &sources, &settings, out)?;
+            }
+            if only != Some(OnlyFile::Llms) {
+                generate_llms_full_txt(&member_root, &member.info, &sources, &settings, out)?;
+                generate_doc_examples(&member_root, &member.info, &sources, &settings, out)?;
+            }
+        }
+    }
+
+    let settings = settings_for(project_info)?;
+    if project_info.is_virtual_workspace() {
+        // 仮想ワークスペースのルートにはインデックスのみ生成
+        if only != Some(OnlyFile::Full) {
+            generate_workspace_index(project_root, project_info, &settings, out)?;
+        }
+        return Ok(format!(
+            "Generated llms.txt index and files for {} workspace members",
+            project_info
+                .workspace
+                .as_ref()
+                .map_or(0, |ws| ws.members.len())
+        ));
+    }
+
+    // クレートルートから mod 宣言をたどってソースファイルを収集し、各ファイルの生成で共有する
+    let sources = discover_module_files(project_root, project_info, settings.strict)?;
 
     // llms.txt を生成
-    generate_llms_txt(project_root, &project_info)?;
+    if only != Some(OnlyFile::Full) {
+        generate_llms_txt(project_root, project_info, &sources, &settings, out)?;
+    }
 
     // llms-full.txt を生成
-    generate_llms_full_txt(project_root, &project_info)?;
+    if only != Some(OnlyFile::Llms) {
+        generate_llms_full_txt(project_root, project_info, &sources, &settings, out)?;
+        generate_doc_examples(project_root, project_info, &sources, &settings, out)?;
+    }
 
-    println!("Generated llms.txt and llms-full.txt");
-    Ok(())
+    let full = if settings.chunks.is_some() {
+        "llms-full/ chunks"
+    } else {
+        "llms-full.txt"
+    };
+    match only {
+        Some(OnlyFile::Llms) => Ok("Generated llms.txt".to_string()),
+        Some(OnlyFile::Full) => Ok(format!("Generated {}", full)),
+        None => Ok(format!("Generated llms.txt and {}", full)),
+    }
+}
+
+/// `--format json` の場合は各クレートの llms.json のみを生成する
+fn generate_json(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    settings_for: &impl Fn(&ProjectInfo) -> Result<GeneratorSettings>,
+    out: &mut OutputWriter,
+) -> Result<String> {
+    if let Some(workspace) = &project_info.workspace {
+        for member in &workspace.members {
+            let member_root = project_root.join(&member.path);
+            let settings = settings_for(&member.info)?;
+            let sources = discover_module_files(&member_root, &member.info, settings.strict)?;
+            generate_llms_json(&member_root, &member.info, &sources, &settings, out)?;
+        }
+    }
+
+    if project_info.is_virtual_workspace() {
+        return Ok(format!(
+            "Generated llms.json for {} workspace members",
+            project_info
+                .workspace
+                .as_ref()
+                .map_or(0, |ws| ws.members.len())
+        ));
+    }
+
+    let settings = settings_for(project_info)?;
+    let sources = discover_module_files(project_root, project_info, settings.strict)?;
+    generate_llms_json(project_root, project_info, &sources, &settings, out)?;
+    Ok("Generated llms.json".to_string())
 }
@@ -0,0 +1,95 @@
+use serde::Serialize;
+
+/// 抽出した API の中間モデル。`--format json` ではこれをそのまま出力する
+#[derive(Debug, Clone, Serialize)]
+pub struct CrateModel {
+    pub name: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    /// モジュールツリーの順（ターゲットごと、モジュールパス順）
+    pub modules: Vec<ModuleModel>,
+    /// 構文エラーのため出力に含まれていないファイル
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_files: Vec<SkippedFileModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedFileModel {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleModel {
+    /// `crate` または `a::b`
+    pub path: String,
+    /// ライブラリ以外のターゲットの表示名（例: `bin tool`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub source_file: String,
+    /// `//!` と mod 宣言の `///` を合わせたモジュールの docs
+    pub docs: Option<String>,
+    pub items: Vec<ItemModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemModel {
+    pub kind: ItemKind,
+    /// 公開パス（例: `config::Config`、メソッドは `Config::new`、トレイト実装は `<Config as Default>::default`）
+    pub path: String,
+    /// llms-full.txt のコードブロックと同じシグネチャ
+    pub signature: String,
+    pub docs: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cfg: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub derives: Vec<String>,
+    pub span: Option<Span>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Function,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    TraitAlias,
+    Const,
+    Static,
+    TypeAlias,
+    Macro,
+    /// `#[proc_macro]` の関数形式マクロ
+    ProcMacro,
+    /// `#[proc_macro_attribute]`
+    AttributeMacro,
+    /// `#[proc_macro_derive]`
+    DeriveMacro,
+    ReExport,
+    ExternCrate,
+    ForeignFunction,
+    Method,
+    AssociatedConst,
+    AssociatedType,
+}
+
+/// ソース上の位置（行は 1 始まり）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+impl Span {
+    pub fn new(file: &str, span: proc_macro2::Span) -> Self {
+        Span {
+            file: file.to_string(),
+            start_line: span.start().line,
+            end_line: span.end().line,
+        }
+    }
+}
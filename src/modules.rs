@@ -0,0 +1,552 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::project_info::ProjectInfo;
+use crate::visitors::is_doc_hidden;
+
+/// ターゲットの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    Lib,
+    Bin,
+}
+
+/// クレートルートとなるソースファイル
+#[derive(Debug, Clone)]
+pub struct CrateTarget {
+    pub kind: TargetKind,
+    /// プロジェクトルートからの相対パス
+    pub root: PathBuf,
+}
+
+/// `mod` 宣言をたどって見つかったモジュールファイル
+pub struct ModuleFile {
+    /// プロジェクトルートからの相対パス
+    pub relative_path: PathBuf,
+    /// クレートルートからのモジュールパス（クレートルート自身は空）
+    pub module_path: Vec<String>,
+    /// このファイルが属するターゲットの種類
+    pub kind: TargetKind,
+    /// 自身または祖先のモジュールに `#[doc(hidden)]` が付いているかどうか
+    pub doc_hidden: bool,
+    pub syntax: syn::File,
+}
+
+/// Cargo.toml の `[lib]` / `[[bin]]` と規約上のパスからクレートルートを列挙する
+pub fn discover_targets(project_root: &Path, project_info: &ProjectInfo) -> Vec<CrateTarget> {
+    let mut targets: Vec<CrateTarget> = Vec::new();
+
+    // ライブラリターゲット
+    let lib = project_info.targets.lib.as_ref();
+    let lib_path = lib.and_then(|t| t.path.as_deref()).unwrap_or("src/lib.rs");
+    if project_root.join(lib_path).is_file() {
+        targets.push(CrateTarget {
+            kind: TargetKind::Lib,
+            root: normalize_path(Path::new(lib_path)),
+        });
+    }
+
+    // 明示的に宣言されたバイナリターゲット
+    for bin in &project_info.targets.bins {
+        let path = match (&bin.path, &bin.name) {
+            (Some(path), _) => Some(PathBuf::from(path)),
+            (None, Some(name)) => [
+                PathBuf::from(format!("src/bin/{}.rs", name)),
+                PathBuf::from(format!("src/bin/{}/main.rs", name)),
+                PathBuf::from("src/main.rs"),
+            ]
+            .into_iter()
+            .find(|p| project_root.join(p).is_file()),
+            (None, None) => None,
+        };
+        if let Some(path) = path {
+            push_bin_target(&mut targets, project_root, &path);
+        }
+    }
+
+    // 規約に基づくバイナリターゲットの自動検出
+    if project_info.targets.autobins {
+        push_bin_target(&mut targets, project_root, Path::new("src/main.rs"));
+
+        if let Ok(entries) = fs::read_dir(project_root.join("src/bin")) {
+            let mut bin_paths: Vec<PathBuf> = entries
+                .filter_map(|e| e.ok())
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let bin_path = PathBuf::from("src/bin").join(path.file_name()?);
+                    if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+                        Some(bin_path)
+                    } else if path.join("main.rs").is_file() {
+                        Some(bin_path.join("main.rs"))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            bin_paths.sort();
+            for path in bin_paths {
+                push_bin_target(&mut targets, project_root, &path);
+            }
+        }
+    }
+
+    targets
+}
+
+fn push_bin_target(targets: &mut Vec<CrateTarget>, project_root: &Path, path: &Path) {
+    let root = normalize_path(path);
+    if !project_root.join(&root).is_file() || targets.iter().any(|t| t.root == root) {
+        return;
+    }
+    targets.push(CrateTarget {
+        kind: TargetKind::Bin,
+        root,
+    });
+}
+
+/// 構文エラーのため読み飛ばしたソースファイル
+#[derive(Debug, Clone)]
+pub struct SkippedFile {
+    /// プロジェクトルートからの相対パス
+    pub relative_path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl SkippedFile {
+    /// `src/foo.rs:12:5` 形式の位置
+    pub fn location(&self) -> String {
+        format!(
+            "{}:{}:{}",
+            self.relative_path.display(),
+            self.line,
+            self.column
+        )
+    }
+}
+
+/// クレートのソースファイルの収集結果。警告が重複しないよう、クレートごとに 1 度だけ収集して
+/// 各出力の生成で共有する
+pub struct CrateSources {
+    pub module_files: Vec<ModuleFile>,
+    /// 構文エラーのため読み飛ばしたファイル
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// モジュールファイルの探索状態
+struct Discovery {
+    visited: HashSet<PathBuf>,
+    skipped: Vec<SkippedFile>,
+    /// 構文エラーがあれば読み飛ばさずにエラーにする
+    strict: bool,
+}
+
+/// 全ターゲットのモジュールファイルを収集する。
+/// `strict` でない場合、構文エラーのあるファイル（とその子モジュール）は警告を出して読み飛ばす
+pub fn discover_module_files(
+    project_root: &Path,
+    project_info: &ProjectInfo,
+    strict: bool,
+) -> Result<CrateSources> {
+    let mut files = Vec::new();
+    let mut discovery = Discovery {
+        visited: HashSet::new(),
+        skipped: Vec::new(),
+        strict,
+    };
+
+    for target in discover_targets(project_root, project_info) {
+        collect_target_modules(project_root, &target, &mut discovery, &mut files)?;
+    }
+
+    Ok(CrateSources {
+        module_files: files,
+        skipped: discovery.skipped,
+    })
+}
+
+/// クレートルートから `mod` 宣言をたどってモジュールファイルを収集する
+fn collect_target_modules(
+    project_root: &Path,
+    target: &CrateTarget,
+    discovery: &mut Discovery,
+    files: &mut Vec<ModuleFile>,
+) -> Result<()> {
+    // クレートルートは mod.rs と同様に自身のディレクトリを所有する
+    let dir = target.root.parent().unwrap_or(Path::new("")).to_path_buf();
+    let context = ModContext {
+        kind: target.kind,
+        module_path: Vec::new(),
+        module_dir: dir.clone(),
+        path_base: dir,
+        doc_hidden: false,
+    };
+    collect_module_file(project_root, &target.root, context, discovery, files)
+}
+
+fn collect_module_file(
+    project_root: &Path,
+    relative_path: &Path,
+    context: ModContext,
+    discovery: &mut Discovery,
+    files: &mut Vec<ModuleFile>,
+) -> Result<()> {
+    if !discovery.visited.insert(relative_path.to_path_buf()) {
+        return Ok(());
+    }
+
+    let full_path = project_root.join(relative_path);
+    let source = fs::read_to_string(&full_path)
+        .with_context(|| format!("Failed to read {}", full_path.display()))?;
+    let syntax = match syn::parse_file(&source) {
+        Ok(syntax) => syntax,
+        Err(err) => {
+            let start = err.span().start();
+            let skipped = SkippedFile {
+                relative_path: relative_path.to_path_buf(),
+                line: start.line,
+                column: start.column + 1,
+                message: err.to_string(),
+            };
+            if discovery.strict {
+                anyhow::bail!("Failed to parse {}: {}", skipped.location(), err);
+            }
+            eprintln!(
+                "warning: skipping {}: {} (use --strict to fail instead)",
+                skipped.location(),
+                skipped.message
+            );
+            discovery.skipped.push(skipped);
+            return Ok(());
+        }
+    };
+
+    // ファイル先頭の `#![doc(hidden)]` は子モジュールにも引き継ぐ
+    let mut context = context;
+    context.doc_hidden |= is_doc_hidden(&syntax.attrs);
+
+    // 親モジュールが子モジュールより先に並ぶようにする
+    let mut children = Vec::new();
+    collect_mod_declarations(
+        project_root,
+        &syntax.items,
+        &context,
+        discovery,
+        &mut children,
+    )?;
+
+    files.push(ModuleFile {
+        relative_path: relative_path.to_path_buf(),
+        module_path: context.module_path,
+        kind: context.kind,
+        doc_hidden: context.doc_hidden,
+        syntax,
+    });
+    files.extend(children);
+    Ok(())
+}
+
+/// `mod` 宣言を解決するための位置情報
+struct ModContext {
+    kind: TargetKind,
+    module_path: Vec<String>,
+    /// 子モジュールのファイルを探すディレクトリ
+    module_dir: PathBuf,
+    /// `#[path]` 属性の基準ディレクトリ
+    path_base: PathBuf,
+    doc_hidden: bool,
+}
+
+fn collect_mod_declarations(
+    project_root: &Path,
+    items: &[syn::Item],
+    context: &ModContext,
+    discovery: &mut Discovery,
+    files: &mut Vec<ModuleFile>,
+) -> Result<()> {
+    for item in items {
+        let syn::Item::Mod(item_mod) = item else {
+            continue;
+        };
+
+        let name = item_mod.ident.to_string();
+        let mut module_path = context.module_path.clone();
+        module_path.push(name.clone());
+        let path_attr = extract_path_attribute(&item_mod.attrs);
+        let doc_hidden = context.doc_hidden || is_doc_hidden(&item_mod.attrs);
+
+        match &item_mod.content {
+            // インラインモジュール: 中の `mod` 宣言はモジュール名のディレクトリ以下で解決される
+            Some((_, inline_items)) => {
+                let module_dir = match &path_attr {
+                    Some(path) => context.module_dir.join(path),
+                    None => context.module_dir.join(&name),
+                };
+                let inline_context = ModContext {
+                    kind: context.kind,
+                    module_path,
+                    path_base: module_dir.clone(),
+                    module_dir,
+                    doc_hidden,
+                };
+                collect_mod_declarations(
+                    project_root,
+                    inline_items,
+                    &inline_context,
+                    discovery,
+                    files,
+                )?;
+            }
+            // 外部ファイルのモジュール
+            None => {
+                let resolved = match &path_attr {
+                    Some(path) => {
+                        let file = normalize_path(&context.path_base.join(path));
+                        // #[path] で読み込まれたファイルは mod.rs と同様に自身のディレクトリを所有する
+                        let dir = file.parent().unwrap_or(Path::new("")).to_path_buf();
+                        Some((file, dir))
+                    }
+                    None => {
+                        let module_dir = context.module_dir.join(&name);
+                        [
+                            context.module_dir.join(format!("{}.rs", name)),
+                            module_dir.join("mod.rs"),
+                        ]
+                        .into_iter()
+                        .map(|p| normalize_path(&p))
+                        .find(|p| project_root.join(p).is_file())
+                        .map(|file| (file, normalize_path(&module_dir)))
+                    }
+                };
+
+                match resolved {
+                    Some((file, dir)) if project_root.join(&file).is_file() => {
+                        let file_context = ModContext {
+                            kind: context.kind,
+                            module_path,
+                            module_dir: dir,
+                            path_base: file.parent().unwrap_or(Path::new("")).to_path_buf(),
+                            doc_hidden,
+                        };
+                        collect_module_file(project_root, &file, file_context, discovery, files)?;
+                    }
+                    _ => {
+                        eprintln!(
+                            "warning: could not find the source file for `mod {}` ({})",
+                            name,
+                            module_path.join("::")
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `#[path = "..."]` 属性の値を取得する
+fn extract_path_attribute(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| {
+        let meta = attr.meta.require_name_value().ok()?;
+        if !meta.path.is_ident("path") {
+            return None;
+        }
+        match &meta.value {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit_str),
+                ..
+            }) => Some(lit_str.value()),
+            _ => None,
+        }
+    })
+}
+
+/// `.` や `..` を取り除いたパスに正規化する
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn module_paths(files: &[ModuleFile]) -> Vec<(String, String)> {
+        files
+            .iter()
+            .map(|f| {
+                (
+                    f.relative_path.to_string_lossy().replace('\\', "/"),
+                    f.module_path.join("::"),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_discover_module_files_follows_mod_declarations() {
+        let temp_dir = std::env::temp_dir().join("cargo_llms_txt_test_modules_follow");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        write_file(&temp_dir, "src/lib.rs", "pub mod a;\nmod b;\n");
+        write_file(&temp_dir, "src/a.rs", "pub mod nested;\n");
+        write_file(&temp_dir, "src/a/nested.rs", "pub fn f() {}\n");
+        write_file(&temp_dir, "src/b/mod.rs", "pub mod c;\n");
+        write_file(&temp_dir, "src/b/c.rs", "pub struct C;\n");
+        write_file(&temp_dir, "src/dead.rs", "pub fn unused() {}\n");
+
+        let files = discover_module_files(&temp_dir, &ProjectInfo::default(), false)
+            .unwrap()
+            .module_files;
+
+        assert_eq!(
+            module_paths(&files),
+            vec![
+                ("src/lib.rs".to_string(), "".to_string()),
+                ("src/a.rs".to_string(), "a".to_string()),
+                ("src/a/nested.rs".to_string(), "a::nested".to_string()),
+                ("src/b/mod.rs".to_string(), "b".to_string()),
+                ("src/b/c.rs".to_string(), "b::c".to_string()),
+            ]
+        );
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_discover_module_files_skips_unparseable_files() {
+        let temp_dir = std::env::temp_dir().join("cargo_llms_txt_test_modules_skip");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        write_file(&temp_dir, "src/lib.rs", "pub mod good;\npub mod broken;\n");
+        write_file(&temp_dir, "src/good.rs", "pub fn ok() {}\n");
+        write_file(
+            &temp_dir,
+            "src/broken.rs",
+            "pub mod child;\n\npub fn broken( {}\n",
+        );
+        write_file(&temp_dir, "src/broken/child.rs", "pub fn c() {}\n");
+
+        let CrateSources {
+            module_files: files,
+            skipped,
+        } = discover_module_files(&temp_dir, &ProjectInfo::default(), false).unwrap();
+
+        // 構文エラーのあるファイルは子モジュールごと読み飛ばす
+        assert_eq!(
+            module_paths(&files),
+            vec![
+                ("src/lib.rs".to_string(), "".to_string()),
+                ("src/good.rs".to_string(), "good".to_string()),
+            ]
+        );
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].relative_path, PathBuf::from("src/broken.rs"));
+        assert_eq!(skipped[0].line, 3);
+        assert!(skipped[0].location().starts_with("src/broken.rs:3:"));
+
+        let err = discover_module_files(&temp_dir, &ProjectInfo::default(), true)
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .starts_with("Failed to parse src/broken.rs:3:"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_discover_module_files_path_attribute_and_inline_modules() {
+        let temp_dir = std::env::temp_dir().join("cargo_llms_txt_test_modules_path_attr");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        write_file(
+            &temp_dir,
+            "src/lib.rs",
+            "#[path = \"platform/unix_impl.rs\"]\npub mod sys;\npub mod outer {\n    pub mod inner;\n}\n",
+        );
+        write_file(&temp_dir, "src/platform/unix_impl.rs", "pub mod helper;\n");
+        write_file(&temp_dir, "src/platform/helper.rs", "pub fn h() {}\n");
+        write_file(&temp_dir, "src/outer/inner.rs", "pub fn i() {}\n");
+
+        let files = discover_module_files(&temp_dir, &ProjectInfo::default(), false)
+            .unwrap()
+            .module_files;
+
+        assert_eq!(
+            module_paths(&files),
+            vec![
+                ("src/lib.rs".to_string(), "".to_string()),
+                ("src/platform/unix_impl.rs".to_string(), "sys".to_string()),
+                (
+                    "src/platform/helper.rs".to_string(),
+                    "sys::helper".to_string()
+                ),
+                ("src/outer/inner.rs".to_string(), "outer::inner".to_string()),
+            ]
+        );
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_discover_targets() {
+        let temp_dir = std::env::temp_dir().join("cargo_llms_txt_test_modules_targets");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        write_file(&temp_dir, "src/lib.rs", "");
+        write_file(&temp_dir, "src/main.rs", "fn main() {}\n");
+        write_file(&temp_dir, "src/bin/tool.rs", "fn main() {}\n");
+        write_file(&temp_dir, "src/bin/multi/main.rs", "fn main() {}\n");
+        write_file(&temp_dir, "tools/custom.rs", "fn main() {}\n");
+        write_file(&temp_dir, "build.rs", "fn main() {}\n");
+
+        let mut project_info = ProjectInfo::default();
+        project_info
+            .targets
+            .bins
+            .push(crate::project_info::TargetInfo {
+                name: Some("custom".to_string()),
+                path: Some("tools/custom.rs".to_string()),
+            });
+        let targets = discover_targets(&temp_dir, &project_info);
+
+        let roots: Vec<_> = targets
+            .iter()
+            .map(|t| t.root.to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert_eq!(
+            roots,
+            vec![
+                "src/lib.rs",
+                "tools/custom.rs",
+                "src/main.rs",
+                "src/bin/multi/main.rs",
+                "src/bin/tool.rs",
+            ]
+        );
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
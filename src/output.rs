@@ -0,0 +1,159 @@
+use anyhow::Result;
+use similar::TextDiff;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 生成したファイルの出力方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputMode {
+    /// ファイルに書き込む
+    #[default]
+    Write,
+    /// `--check`: ディスク上のファイルと比較するだけで書き込まない
+    Check,
+    /// `--stdout`: ファイルには書き込まず標準出力に出力する
+    Stdout,
+}
+
+/// 生成したファイルの書き込み先
+#[derive(Debug, Default)]
+pub struct OutputWriter {
+    mode: OutputMode,
+    /// `--check` で内容が異なっていたファイル
+    pub stale: Vec<PathBuf>,
+}
+
+impl OutputWriter {
+    pub fn new(mode: OutputMode) -> Self {
+        OutputWriter {
+            mode,
+            stale: Vec::new(),
+        }
+    }
+
+    /// ファイルを書き込む。`--check` では内容を比較し、異なれば unified diff を表示する
+    pub fn write(&mut self, path: &Path, content: &str) -> Result<()> {
+        match self.mode {
+            OutputMode::Write => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, content)?;
+                return Ok(());
+            }
+            OutputMode::Stdout => {
+                print!("{}", content);
+                return Ok(());
+            }
+            OutputMode::Check => {}
+        }
+
+        let current = fs::read_to_string(path).unwrap_or_default();
+        if let Some(diff) = stale_diff(path, &current, content) {
+            print!("{}", diff);
+            self.stale.push(path.to_path_buf());
+        }
+        Ok(())
+    }
+
+    /// 出力ディレクトリを作成する。`--check` と `--stdout` では何もしない
+    pub fn create_dir(&mut self, dir: &Path) -> Result<()> {
+        if self.mode == OutputMode::Write {
+            fs::create_dir_all(dir)?;
+        }
+        Ok(())
+    }
+
+    /// 今回生成されなかった古いファイルを削除する。`--check` では削除されるファイルの差分を表示する
+    pub fn remove(&mut self, path: &Path) -> Result<()> {
+        match self.mode {
+            OutputMode::Write => {
+                fs::remove_file(path)?;
+                return Ok(());
+            }
+            OutputMode::Stdout => return Ok(()),
+            OutputMode::Check => {}
+        }
+
+        let current = fs::read_to_string(path).unwrap_or_default();
+        if let Some(diff) = stale_diff(path, &current, "") {
+            print!("{}", diff);
+        }
+        self.stale.push(path.to_path_buf());
+        Ok(())
+    }
+}
+
+/// `Generated:` の行を除いて比較し、異なる場合は unified diff を返す
+fn stale_diff(path: &Path, current: &str, generated: &str) -> Option<String> {
+    let current = mask_timestamp(current);
+    let generated = mask_timestamp(generated);
+    if current == generated {
+        return None;
+    }
+
+    let path = path.display().to_string();
+    Some(
+        TextDiff::from_lines(&current, &generated)
+            .unified_diff()
+            .header(&path, &path)
+            .to_string(),
+    )
+}
+
+/// 生成日時は実行のたびに変わるので比較の対象から外す。
+/// 対象は最初の `---` までのヘッダーにある最初の `Generated: ` の行だけ
+fn mask_timestamp(content: &str) -> String {
+    let mut masked = String::with_capacity(content.len());
+    let mut in_header = true;
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\r', '\n']);
+        if in_header && text.starts_with("Generated: ") {
+            masked.push_str("Generated: <timestamp>");
+            masked.push_str(&line[text.len()..]);
+            in_header = false;
+            continue;
+        }
+        if text == "---" {
+            in_header = false;
+        }
+        masked.push_str(line);
+    }
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_diff_ignores_generated_line() {
+        let path = Path::new("llms.txt");
+        let old = "# demo\n\nGenerated: 2024-01-01 00:00:00 UTC  \n\n- fn a\n";
+        let same = "# demo\n\nGenerated: 2025-06-30 12:34:56 UTC  \n\n- fn a\n";
+        assert_eq!(stale_diff(path, old, same), None);
+
+        let changed = "# demo\n\nGenerated: 2025-06-30 12:34:56 UTC  \n\n- fn a\n- fn b\n";
+        let diff = stale_diff(path, old, changed).unwrap();
+        assert!(diff.starts_with("--- llms.txt\n+++ llms.txt\n"));
+        assert!(diff.contains("+- fn b\n"));
+        assert!(!diff.contains("2025-06-30"));
+    }
+
+    #[test]
+    fn test_stale_diff_masks_only_header_timestamp() {
+        let path = Path::new("llms-full.txt");
+        let old = "# demo\n\nGenerated: 2024-01-01 00:00:00 UTC  \n\n---\n\nGenerated: by hand\n";
+        let changed =
+            "# demo\n\nGenerated: 2025-06-30 12:34:56 UTC  \n\n---\n\nGenerated: by a script\n";
+        let diff = stale_diff(path, old, changed).unwrap();
+        assert!(diff.contains("-Generated: by hand\n"));
+        assert!(diff.contains("+Generated: by a script\n"));
+
+        // 末尾の行でも改行を付け足さない
+        assert_eq!(
+            mask_timestamp("# demo\nGenerated: 2024-01-01 00:00:00 UTC"),
+            "# demo\nGenerated: <timestamp>"
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use anyhow::Result;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use crate::config::{load_config, merge_tables, Config};
 
 #[derive(Default)]
 pub struct ProjectInfo {
@@ -15,7 +17,18 @@ pub struct ProjectInfo {
     pub homepage: Option<String>,
     pub keywords: Option<Vec<String>>,
     pub dependencies: Option<Vec<DependencyInfo>>,
-    pub features: Option<HashMap<String, Vec<String>>>,
+    pub features: Option<BTreeMap<String, Vec<String>>>,
+    pub workspace: Option<WorkspaceInfo>,
+    pub targets: TargetsInfo,
+    /// llms-txt の設定
+    pub config: Config,
+}
+
+impl ProjectInfo {
+    /// `[package]` を持たない仮想ワークスペースのルートかどうか
+    pub fn is_virtual_workspace(&self) -> bool {
+        self.workspace.is_some() && self.name.is_none()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -25,9 +38,86 @@ pub struct DependencyInfo {
     pub features: Option<Vec<String>>,
 }
 
+/// `[lib]` / `[[bin]]` / `[[example]]` セクションのターゲット定義
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TargetInfo {
+    pub name: Option<String>,
+    pub path: Option<String>,
+}
+
+/// Cargo.toml で宣言されたビルドターゲット
+#[derive(Debug, Clone)]
+pub struct TargetsInfo {
+    pub lib: Option<TargetInfo>,
+    pub bins: Vec<TargetInfo>,
+    /// `autobins = false` の場合は src/bin を自動検出しない
+    pub autobins: bool,
+    pub examples: Vec<TargetInfo>,
+    /// `autoexamples = false` の場合は examples/ を自動検出しない
+    pub autoexamples: bool,
+}
+
+impl Default for TargetsInfo {
+    fn default() -> Self {
+        Self {
+            lib: None,
+            bins: Vec::new(),
+            autobins: true,
+            examples: Vec::new(),
+            autoexamples: true,
+        }
+    }
+}
+
+/// `[workspace]` セクションから解決したワークスペース情報
+#[derive(Default)]
+pub struct WorkspaceInfo {
+    pub members: Vec<WorkspaceMember>,
+}
+
+/// ワークスペースのメンバークレート
+pub struct WorkspaceMember {
+    /// ワークスペースルートからの相対パス
+    pub path: PathBuf,
+    pub info: ProjectInfo,
+}
+
+/// `version.workspace = true` のようにワークスペースから継承できるフィールド
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Inheritable<T> {
+    Value(T),
+    Workspace { workspace: bool },
+}
+
+impl<T: Clone> Inheritable<T> {
+    fn resolve(self, inherited: Option<&T>) -> Option<T> {
+        match self {
+            Inheritable::Value(value) => Some(value),
+            Inheritable::Workspace { workspace: true } => inherited.cloned(),
+            Inheritable::Workspace { workspace: false } => None,
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct CargoPackage {
     name: Option<String>,
+    version: Option<Inheritable<String>>,
+    description: Option<Inheritable<String>>,
+    authors: Option<Inheritable<Vec<String>>>,
+    license: Option<Inheritable<String>>,
+    repository: Option<Inheritable<String>>,
+    homepage: Option<Inheritable<String>>,
+    keywords: Option<Inheritable<Vec<String>>>,
+    autobins: Option<bool>,
+    autoexamples: Option<bool>,
+    metadata: Option<toml::Table>,
+}
+
+/// `[workspace.package]` で定義される継承元の値
+#[derive(Deserialize, Default)]
+struct WorkspacePackage {
     version: Option<String>,
     description: Option<String>,
     authors: Option<Vec<String>>,
@@ -37,34 +127,78 @@ struct CargoPackage {
     keywords: Option<Vec<String>>,
 }
 
+#[derive(Deserialize, Default)]
+struct CargoWorkspace {
+    members: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    package: Option<WorkspacePackage>,
+    dependencies: Option<HashMap<String, toml::Value>>,
+    metadata: Option<toml::Table>,
+}
+
 #[derive(Deserialize)]
 struct CargoToml {
     package: Option<CargoPackage>,
-    dependencies: Option<HashMap<String, toml::Value>>,
-    features: Option<HashMap<String, Vec<String>>>,
+    // 出力が実行ごとに変わらないよう、名前順に並ぶ BTreeMap で受け取る
+    dependencies: Option<BTreeMap<String, toml::Value>>,
+    features: Option<BTreeMap<String, Vec<String>>>,
+    workspace: Option<CargoWorkspace>,
+    lib: Option<TargetInfo>,
+    bin: Option<Vec<TargetInfo>>,
+    example: Option<Vec<TargetInfo>>,
 }
 
 pub fn parse_project_info(content: &str) -> Result<ProjectInfo> {
+    let mut cargo_toml: CargoToml = toml::from_str(content)?;
+    // ルートパッケージ自身も [workspace.package] から継承できる
+    let workspace = cargo_toml.workspace.take();
+    Ok(build_project_info(cargo_toml, workspace.as_ref()))
+}
+
+fn build_project_info(cargo_toml: CargoToml, workspace: Option<&CargoWorkspace>) -> ProjectInfo {
     let mut info = ProjectInfo::default();
-    let cargo_toml: CargoToml = toml::from_str(content)?;
+    let defaults = workspace.and_then(|ws| ws.package.as_ref());
+    let workspace_deps = workspace.and_then(|ws| ws.dependencies.as_ref());
 
     if let Some(package) = cargo_toml.package {
         info.name = package.name;
-        info.description = package.description;
-        info.version = package.version;
-        info.license = package.license;
-        info.repository = package.repository;
-        info.homepage = package.homepage;
-        info.keywords = package.keywords;
+        info.description = package
+            .description
+            .and_then(|v| v.resolve(defaults.and_then(|d| d.description.as_ref())));
+        info.version = package
+            .version
+            .and_then(|v| v.resolve(defaults.and_then(|d| d.version.as_ref())));
+        info.license = package
+            .license
+            .and_then(|v| v.resolve(defaults.and_then(|d| d.license.as_ref())));
+        info.repository = package
+            .repository
+            .and_then(|v| v.resolve(defaults.and_then(|d| d.repository.as_ref())));
+        info.homepage = package
+            .homepage
+            .and_then(|v| v.resolve(defaults.and_then(|d| d.homepage.as_ref())));
+        info.keywords = package
+            .keywords
+            .and_then(|v| v.resolve(defaults.and_then(|d| d.keywords.as_ref())));
+        info.targets.autobins = package.autobins.unwrap_or(true);
+        info.targets.autoexamples = package.autoexamples.unwrap_or(true);
 
         // authorsを文字列に変換
-        if let Some(authors) = package.authors {
+        let authors = package
+            .authors
+            .and_then(|v| v.resolve(defaults.and_then(|d| d.authors.as_ref())));
+        if let Some(authors) = authors {
             if !authors.is_empty() {
                 info.authors = Some(authors.join(", "));
             }
         }
     }
 
+    // ターゲット定義
+    info.targets.lib = cargo_toml.lib;
+    info.targets.bins = cargo_toml.bin.unwrap_or_default();
+    info.targets.examples = cargo_toml.example.unwrap_or_default();
+
     // dependenciesを解析
     if let Some(deps) = cargo_toml.dependencies {
         let mut dependency_infos = Vec::new();
@@ -80,27 +214,35 @@ pub fn parse_project_info(content: &str) -> Result<ProjectInfo> {
                     dep_info.version = Some(version);
                 }
                 toml::Value::Table(table) => {
+                    // `{ workspace = true }` の場合は [workspace.dependencies] から補完
+                    if let Some(toml::Value::Boolean(true)) = table.get("workspace") {
+                        match workspace_deps.and_then(|deps| deps.get(&name)) {
+                            Some(toml::Value::String(v)) => dep_info.version = Some(v.clone()),
+                            Some(toml::Value::Table(ws_table)) => {
+                                if let Some(toml::Value::String(v)) = ws_table.get("version") {
+                                    dep_info.version = Some(v.clone());
+                                }
+                                dep_info.features = extract_features(ws_table);
+                            }
+                            _ => {}
+                        }
+                    }
                     if let Some(toml::Value::String(v)) = table.get("version") {
                         dep_info.version = Some(v.clone());
                     }
-                    if let Some(toml::Value::Array(feat_array)) = table.get("features") {
-                        let features_vec: Vec<String> = feat_array
-                            .iter()
-                            .filter_map(|f| {
-                                if let toml::Value::String(s) = f {
-                                    Some(s.clone())
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect();
-                        if !features_vec.is_empty() {
-                            dep_info.features = Some(features_vec);
-                        }
+                    if let Some(features_vec) = extract_features(&table) {
+                        dep_info
+                            .features
+                            .get_or_insert_with(Vec::new)
+                            .extend(features_vec);
                     }
                 }
                 _ => {}
             }
+            if let Some(features) = &mut dep_info.features {
+                features.sort();
+                features.dedup();
+            }
             dependency_infos.push(dep_info);
         }
         if !dependency_infos.is_empty() {
@@ -115,18 +257,124 @@ pub fn parse_project_info(content: &str) -> Result<ProjectInfo> {
         }
     }
 
-    Ok(info)
+    info
+}
+
+fn extract_features(table: &toml::value::Table) -> Option<Vec<String>> {
+    if let Some(toml::Value::Array(feat_array)) = table.get("features") {
+        let features_vec: Vec<String> = feat_array
+            .iter()
+            .filter_map(|f| {
+                if let toml::Value::String(s) = f {
+                    Some(s.clone())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        if !features_vec.is_empty() {
+            return Some(features_vec);
+        }
+    }
+    None
 }
 
 pub fn get_project_info(project_root: &Path) -> Result<ProjectInfo> {
     let cargo_toml_path = project_root.join("Cargo.toml");
 
-    if cargo_toml_path.exists() {
-        let content = fs::read_to_string(&cargo_toml_path)?;
-        parse_project_info(&content)
-    } else {
-        Ok(ProjectInfo::default())
+    if !cargo_toml_path.exists() {
+        return Ok(ProjectInfo::default());
+    }
+
+    let content = fs::read_to_string(&cargo_toml_path)?;
+    let mut info = parse_project_info(&content)?;
+
+    let cargo_toml: CargoToml = toml::from_str(&content)?;
+    let metadata = llms_txt_metadata(cargo_toml.package.as_ref(), cargo_toml.workspace.as_ref());
+    info.config = load_config(project_root, metadata)?;
+
+    // ワークスペースの場合は各メンバーの情報も取得
+    if let Some(workspace) = cargo_toml.workspace {
+        let mut members = Vec::new();
+        for path in resolve_workspace_members(project_root, &workspace)? {
+            let member_root = project_root.join(&path);
+            let member_content = fs::read_to_string(member_root.join("Cargo.toml"))?;
+            let member_toml: CargoToml = toml::from_str(&member_content)?;
+            let metadata = llms_txt_metadata(member_toml.package.as_ref(), Some(&workspace));
+            let mut member_info = build_project_info(member_toml, Some(&workspace));
+            member_info.config = load_config(&member_root, metadata)?;
+            members.push(WorkspaceMember {
+                info: member_info,
+                path,
+            });
+        }
+        info.workspace = Some(WorkspaceInfo { members });
+    }
+
+    Ok(info)
+}
+
+/// `[workspace.metadata.llms-txt]` に `[package.metadata.llms-txt]` を重ねた設定のテーブル
+fn llms_txt_metadata(
+    package: Option<&CargoPackage>,
+    workspace: Option<&CargoWorkspace>,
+) -> toml::Table {
+    let section = |metadata: Option<&toml::Table>| match metadata?.get("llms-txt") {
+        Some(toml::Value::Table(table)) => Some(table.clone()),
+        _ => None,
+    };
+
+    let mut table = section(workspace.and_then(|ws| ws.metadata.as_ref())).unwrap_or_default();
+    if let Some(package) = section(package.and_then(|p| p.metadata.as_ref())) {
+        merge_tables(&mut table, package);
+    }
+    table
+}
+
+/// `members` のglobを展開し、`exclude` に該当するものを除いたメンバーのパスを返す
+fn resolve_workspace_members(
+    project_root: &Path,
+    workspace: &CargoWorkspace,
+) -> Result<Vec<PathBuf>> {
+    let exclude: Vec<PathBuf> = workspace
+        .exclude
+        .iter()
+        .flatten()
+        .map(|e| normalize_relative_path(Path::new(e)))
+        .collect();
+
+    let escaped_root = glob::Pattern::escape(&project_root.to_string_lossy());
+    let mut members = Vec::new();
+
+    for pattern in workspace.members.iter().flatten() {
+        let full_pattern = format!("{}/{}", escaped_root, pattern);
+        for entry in glob::glob(&full_pattern)? {
+            let member_dir = entry?;
+            if !member_dir.join("Cargo.toml").is_file() {
+                continue;
+            }
+            let relative = normalize_relative_path(member_dir.strip_prefix(project_root)?);
+            // ルートパッケージ自身はメンバーとして扱わない
+            if relative.as_os_str().is_empty() {
+                continue;
+            }
+            if exclude.iter().any(|ex| relative.starts_with(ex)) {
+                continue;
+            }
+            members.push(relative);
+        }
     }
+
+    members.sort();
+    members.dedup();
+    Ok(members)
+}
+
+/// `./crates/foo` のような相対パスから `.` を取り除く
+fn normalize_relative_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .collect()
 }
 
 #[cfg(test)]
@@ -207,6 +455,48 @@ tokio = { version = "1.0", features = ["full", "rt-multi-thread"] }
         );
     }
 
+    #[test]
+    fn test_parse_project_info_sorted() {
+        let content = r#"
+[package]
+name = "test-project"
+version = "1.0.0"
+
+[dependencies]
+tokio = { version = "1.0", features = ["rt", "macros", "rt"] }
+anyhow = "1.0"
+serde = "1.0"
+
+[features]
+std = []
+default = ["std"]
+alloc = []
+"#;
+
+        let info = parse_project_info(content).unwrap();
+
+        // 依存関係と features は Cargo.toml の記述順やハッシュ順によらず名前順になる
+        let names: Vec<_> = info
+            .dependencies
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.name, d.features))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("anyhow".to_string(), None),
+                ("serde".to_string(), None),
+                (
+                    "tokio".to_string(),
+                    Some(vec!["macros".to_string(), "rt".to_string()])
+                ),
+            ]
+        );
+        let features: Vec<_> = info.features.unwrap().into_keys().collect();
+        assert_eq!(features, vec!["alloc", "default", "std"]);
+    }
+
     #[test]
     fn test_parse_project_info_features() {
         let content = r#"
@@ -269,4 +559,101 @@ serde = "1.0"
         let result = parse_project_info(content);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_project_info_workspace_inheritance() {
+        let content = r#"
+[workspace]
+members = ["crates/*"]
+
+[workspace.package]
+version = "2.0.0"
+license = "MIT"
+
+[workspace.dependencies]
+serde = { version = "1.0", features = ["derive"] }
+
+[package]
+name = "root"
+version.workspace = true
+license.workspace = true
+description = "Root package"
+
+[dependencies]
+serde = { workspace = true, features = ["rc"] }
+"#;
+
+        let info = parse_project_info(content).unwrap();
+
+        assert_eq!(info.name, Some("root".to_string()));
+        assert_eq!(info.version, Some("2.0.0".to_string()));
+        assert_eq!(info.license, Some("MIT".to_string()));
+        assert_eq!(info.description, Some("Root package".to_string()));
+
+        let deps = info.dependencies.unwrap();
+        let serde_dep = deps.iter().find(|d| d.name == "serde").unwrap();
+        assert_eq!(serde_dep.version, Some("1.0".to_string()));
+        assert_eq!(
+            serde_dep.features,
+            Some(vec!["derive".to_string(), "rc".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_get_project_info_workspace_members() {
+        let root = Path::new("tests/fixtures/workspace_project");
+        let info = get_project_info(root).unwrap();
+
+        assert!(info.is_virtual_workspace());
+        let workspace = info.workspace.unwrap();
+        let paths: Vec<_> = workspace.members.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("crates/alpha"), PathBuf::from("crates/beta")]
+        );
+
+        let alpha = &workspace.members[0].info;
+        assert_eq!(alpha.name, Some("alpha".to_string()));
+        assert_eq!(alpha.version, Some("0.3.0".to_string()));
+        assert_eq!(
+            alpha.authors,
+            Some("Workspace Author <workspace@example.com>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_llms_txt_metadata_inherits_workspace() {
+        let cargo_toml: CargoToml = toml::from_str(
+            r#"
+[package]
+name = "member"
+
+[package.metadata.llms-txt]
+visibility = "all"
+
+[package.metadata.llms-txt.sections]
+readme = false
+
+[workspace.metadata.llms-txt]
+visibility = "crate"
+exclude = ["src/generated/**"]
+
+[workspace.metadata.llms-txt.sections]
+cargo-toml = false
+"#,
+        )
+        .unwrap();
+
+        let table = llms_txt_metadata(cargo_toml.package.as_ref(), cargo_toml.workspace.as_ref());
+        let config = load_config(Path::new("tests/fixtures/simple_project"), table).unwrap();
+
+        // パッケージの設定がワークスペースの設定をキーごとに上書きする
+        assert_eq!(
+            config.visibility,
+            Some(crate::visitors::VisibilityLevel::All)
+        );
+        assert_eq!(config.exclude, vec!["src/generated/**"]);
+        assert!(!config.sections.readme);
+        assert!(!config.sections.cargo_toml);
+    }
 }
@@ -0,0 +1,571 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use syn::Visibility;
+
+use crate::modules::ModuleFile;
+
+/// `use` やグロブの循環を打ち切るための再帰の上限
+const MAX_RESOLVE_DEPTH: usize = 32;
+
+/// クレートの実際の公開API（`pub use` による再エクスポートを含む）
+#[derive(Default)]
+pub struct PublicApi {
+    /// 定義位置（モジュールパス + 名前）→ 公開パス（短い順）
+    item_paths: HashMap<Vec<String>, Vec<Vec<String>>>,
+    /// クレート外から到達可能なモジュール（定義位置）
+    reachable_modules: HashSet<Vec<String>>,
+    /// クレート内のアイテムを指す `pub use`（モジュールパス, インポートパス）
+    local_reexports: HashSet<(Vec<String>, Vec<String>)>,
+}
+
+/// クレート内の名前解決に使うモジュールごとのスコープ
+#[derive(Default)]
+pub struct CrateScopes {
+    scopes: HashMap<Vec<String>, ModuleScope>,
+}
+
+impl CrateScopes {
+    /// ターゲットのモジュールファイルからスコープを構築する
+    pub fn build<'a>(files: impl IntoIterator<Item = &'a ModuleFile>) -> Self {
+        let mut scopes = HashMap::new();
+        for file in files {
+            collect_scope(&file.syntax.items, &file.module_path, &mut scopes);
+        }
+        CrateScopes { scopes }
+    }
+
+    /// モジュール `from` に書かれたパスをクレート内の定義位置（モジュールパス + 名前）に解決する
+    pub fn resolve(&self, from: &[String], path: &[String]) -> Option<Vec<String>> {
+        let import = Import {
+            is_pub: false,
+            leading_colon: false,
+            path: path.to_vec(),
+            name: path.last().cloned(),
+        };
+        let resolver = Resolver {
+            scopes: &self.scopes,
+        };
+        match resolver.resolve_path(from, &import, 0)? {
+            Resolved::Item {
+                mut module, name, ..
+            } => {
+                module.push(name);
+                Some(module)
+            }
+            Resolved::External => None,
+        }
+    }
+}
+
+impl PublicApi {
+    /// ライブラリターゲットのモジュールファイルから公開APIを計算する
+    pub fn build<'a>(files: impl IntoIterator<Item = &'a ModuleFile>) -> Self {
+        let CrateScopes { scopes } = CrateScopes::build(files);
+        let resolver = Resolver { scopes: &scopes };
+
+        let mut api = PublicApi::default();
+        let mut queue = VecDeque::from([(Vec::<String>::new(), Vec::<String>::new(), Vec::new())]);
+
+        // クレートルートから幅優先でたどり、最も短い公開パスを先に記録する。
+        // 同じモジュールが別名で再エクスポートされている場合はそれぞれのパスでたどる
+        while let Some((module, prefix, ancestors)) = queue.pop_front() {
+            api.reachable_modules.insert(module.clone());
+
+            let mut ancestors: Vec<Vec<String>> = ancestors;
+            ancestors.push(module.clone());
+
+            for (name, resolved) in resolver.exports(&module, 0) {
+                let Resolved::Item {
+                    module: def_module,
+                    name: def_name,
+                    is_module,
+                } = resolved
+                else {
+                    continue;
+                };
+
+                let mut public_path = prefix.clone();
+                public_path.push(name);
+                let mut def_path = def_module;
+                def_path.push(def_name);
+
+                let paths = api.item_paths.entry(def_path.clone()).or_default();
+                if !paths.contains(&public_path) {
+                    paths.push(public_path.clone());
+                }
+                // 再エクスポートの循環はたどらない
+                if is_module && !ancestors.contains(&def_path) {
+                    queue.push_back((def_path, public_path, ancestors.clone()));
+                }
+            }
+        }
+
+        // クレート内のアイテムを再エクスポートしている `pub use` を記録
+        for module in &api.reachable_modules {
+            let Some(scope) = scopes.get(module) else {
+                continue;
+            };
+            for import in scope.imports.iter().filter(|i| i.is_pub) {
+                let is_local = match import.name {
+                    Some(_) => matches!(
+                        resolver.resolve_path(module, import, 0),
+                        Some(Resolved::Item { .. })
+                    ),
+                    None => resolver.resolve_module(module, import, 0).is_some(),
+                };
+                if is_local {
+                    api.local_reexports
+                        .insert((module.clone(), import.path.clone()));
+                }
+            }
+        }
+
+        api
+    }
+
+    /// `pub use` がクレート内のアイテムのみを再エクスポートしているかどうか。
+    /// その場合アイテムは公開パスで出力されるので、`use` 自体は出力しなくてよい
+    pub fn is_local_reexport(&self, module: &[String], item_use: &syn::ItemUse) -> bool {
+        let mut imports = Vec::new();
+        flatten_use_tree(
+            &item_use.tree,
+            Vec::new(),
+            true,
+            item_use.leading_colon.is_some(),
+            &mut imports,
+        );
+        !imports.is_empty()
+            && imports.iter().all(|import| {
+                self.local_reexports
+                    .contains(&(module.to_vec(), import.path.clone()))
+            })
+    }
+
+    /// 定義位置に対応する公開パスを返す。公開APIに含まれない場合は空
+    pub fn public_paths(&self, module: &[String], name: &str) -> &[Vec<String>] {
+        let mut def_path = module.to_vec();
+        def_path.push(name.to_string());
+        self.item_paths
+            .get(&def_path)
+            .map(|paths| paths.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// モジュールがクレート外から到達可能かどうか
+    pub fn is_module_reachable(&self, module: &[String]) -> bool {
+        self.reachable_modules.contains(module)
+    }
+}
+
+/// モジュール内で定義・インポートされている名前
+#[derive(Default)]
+struct ModuleScope {
+    definitions: Vec<Definition>,
+    imports: Vec<Import>,
+}
+
+struct Definition {
+    name: String,
+    is_pub: bool,
+    is_module: bool,
+}
+
+struct Import {
+    is_pub: bool,
+    leading_colon: bool,
+    path: Vec<String>,
+    /// インポートされる名前（グロブの場合は None）
+    name: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Resolved {
+    Item {
+        module: Vec<String>,
+        name: String,
+        is_module: bool,
+    },
+    External,
+}
+
+fn collect_scope(
+    items: &[syn::Item],
+    module_path: &[String],
+    scopes: &mut HashMap<Vec<String>, ModuleScope>,
+) {
+    let mut definitions = Vec::new();
+    let mut imports = Vec::new();
+
+    for item in items {
+        let (ident, vis, is_module) = match item {
+            syn::Item::Fn(i) => (&i.sig.ident, &i.vis, false),
+            syn::Item::Struct(i) => (&i.ident, &i.vis, false),
+            syn::Item::Enum(i) => (&i.ident, &i.vis, false),
+            syn::Item::Trait(i) => (&i.ident, &i.vis, false),
+            syn::Item::TraitAlias(i) => (&i.ident, &i.vis, false),
+            syn::Item::Const(i) => (&i.ident, &i.vis, false),
+            syn::Item::Static(i) => (&i.ident, &i.vis, false),
+            syn::Item::Type(i) => (&i.ident, &i.vis, false),
+            syn::Item::Union(i) => (&i.ident, &i.vis, false),
+            syn::Item::Mod(i) => {
+                if let Some((_, inline_items)) = &i.content {
+                    let mut inline_path = module_path.to_vec();
+                    inline_path.push(i.ident.to_string());
+                    collect_scope(inline_items, &inline_path, scopes);
+                }
+                (&i.ident, &i.vis, true)
+            }
+            syn::Item::ForeignMod(foreign_mod) => {
+                for foreign_item in &foreign_mod.items {
+                    let (ident, vis) = match foreign_item {
+                        syn::ForeignItem::Fn(f) => (&f.sig.ident, &f.vis),
+                        syn::ForeignItem::Static(s) => (&s.ident, &s.vis),
+                        syn::ForeignItem::Type(t) => (&t.ident, &t.vis),
+                        _ => continue,
+                    };
+                    definitions.push(Definition {
+                        name: ident.to_string(),
+                        is_pub: matches!(vis, Visibility::Public(_)),
+                        is_module: false,
+                    });
+                }
+                continue;
+            }
+            syn::Item::Use(item_use) => {
+                flatten_use_tree(
+                    &item_use.tree,
+                    Vec::new(),
+                    matches!(item_use.vis, Visibility::Public(_)),
+                    item_use.leading_colon.is_some(),
+                    &mut imports,
+                );
+                continue;
+            }
+            _ => continue,
+        };
+
+        definitions.push(Definition {
+            name: ident.to_string(),
+            is_pub: matches!(vis, Visibility::Public(_)),
+            is_module,
+        });
+    }
+
+    let scope = scopes.entry(module_path.to_vec()).or_default();
+    scope.definitions.extend(definitions);
+    scope.imports.extend(imports);
+}
+
+fn flatten_use_tree(
+    tree: &syn::UseTree,
+    mut prefix: Vec<String>,
+    is_pub: bool,
+    leading_colon: bool,
+    imports: &mut Vec<Import>,
+) {
+    let mut push = |path: Vec<String>, name: Option<String>| {
+        imports.push(Import {
+            is_pub,
+            leading_colon,
+            path,
+            name,
+        })
+    };
+
+    match tree {
+        syn::UseTree::Path(use_path) => {
+            prefix.push(use_path.ident.to_string());
+            flatten_use_tree(&use_path.tree, prefix, is_pub, leading_colon, imports);
+        }
+        syn::UseTree::Name(use_name) => {
+            // `use a::{self}` は `a` 自身をインポートする
+            if use_name.ident == "self" {
+                let name = prefix.last().cloned();
+                push(prefix, name);
+            } else {
+                let name = use_name.ident.to_string();
+                prefix.push(name.clone());
+                push(prefix, Some(name));
+            }
+        }
+        syn::UseTree::Rename(use_rename) => {
+            if use_rename.ident != "self" {
+                prefix.push(use_rename.ident.to_string());
+            }
+            push(prefix, Some(use_rename.rename.to_string()));
+        }
+        syn::UseTree::Glob(_) => push(prefix, None),
+        syn::UseTree::Group(group) => {
+            for item in &group.items {
+                flatten_use_tree(item, prefix.clone(), is_pub, leading_colon, imports);
+            }
+        }
+    }
+}
+
+struct Resolver<'a> {
+    scopes: &'a HashMap<Vec<String>, ModuleScope>,
+}
+
+impl Resolver<'_> {
+    /// モジュールが外部に公開している名前の一覧
+    fn exports(&self, module: &[String], depth: usize) -> Vec<(String, Resolved)> {
+        let Some(scope) = self.scopes.get(module) else {
+            return Vec::new();
+        };
+        if depth > MAX_RESOLVE_DEPTH {
+            return Vec::new();
+        }
+
+        let mut exports: Vec<(String, Resolved)> = scope
+            .definitions
+            .iter()
+            .filter(|d| d.is_pub)
+            .map(|d| {
+                (
+                    d.name.clone(),
+                    Resolved::Item {
+                        module: module.to_vec(),
+                        name: d.name.clone(),
+                        is_module: d.is_module,
+                    },
+                )
+            })
+            .collect();
+
+        for import in scope.imports.iter().filter(|i| i.is_pub) {
+            match &import.name {
+                Some(name) => {
+                    let resolved = self
+                        .resolve_path(module, import, depth + 1)
+                        .unwrap_or(Resolved::External);
+                    exports.push((name.clone(), resolved));
+                }
+                None => {
+                    // グロブは明示的な名前に隠されない名前のみを再エクスポートする
+                    if let Some(source) = self.resolve_module(module, import, depth + 1) {
+                        for (name, resolved) in self.exports(&source, depth + 1) {
+                            if !exports.iter().any(|(n, _)| *n == name) {
+                                exports.push((name, resolved));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        exports
+    }
+
+    /// モジュール内で名前を解決する
+    fn lookup(
+        &self,
+        module: &[String],
+        name: &str,
+        pub_only: bool,
+        depth: usize,
+    ) -> Option<Resolved> {
+        if depth > MAX_RESOLVE_DEPTH {
+            return None;
+        }
+        let scope = self.scopes.get(module)?;
+
+        if let Some(def) = scope
+            .definitions
+            .iter()
+            .find(|d| d.name == name && (d.is_pub || !pub_only))
+        {
+            return Some(Resolved::Item {
+                module: module.to_vec(),
+                name: def.name.clone(),
+                is_module: def.is_module,
+            });
+        }
+
+        let visible_imports = scope.imports.iter().filter(|i| i.is_pub || !pub_only);
+        for import in visible_imports.clone() {
+            if import.name.as_deref() == Some(name) {
+                return self.resolve_path(module, import, depth + 1);
+            }
+        }
+        for import in visible_imports.filter(|i| i.name.is_none()) {
+            if let Some(source) = self.resolve_module(module, import, depth + 1) {
+                if let Some(resolved) = self.lookup(&source, name, true, depth + 1) {
+                    return Some(resolved);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// `use` のパスを、それが書かれたモジュールを起点に解決する
+    fn resolve_path(&self, from: &[String], import: &Import, depth: usize) -> Option<Resolved> {
+        if import.leading_colon {
+            return Some(Resolved::External);
+        }
+
+        let mut module = from.to_vec();
+        let mut rest = import.path.as_slice();
+        let mut is_relative = true;
+        match rest.first().map(String::as_str) {
+            Some("crate") => {
+                module.clear();
+                rest = &rest[1..];
+                is_relative = false;
+            }
+            Some("self") => {
+                rest = &rest[1..];
+                is_relative = false;
+            }
+            Some("super") => {
+                while rest.first().map(String::as_str) == Some("super") {
+                    module.pop();
+                    rest = &rest[1..];
+                }
+                is_relative = false;
+            }
+            _ => {}
+        }
+
+        // `use crate::a::{self}` のようにモジュール自身を指す場合
+        if rest.is_empty() {
+            let name = module.pop()?;
+            return Some(Resolved::Item {
+                module,
+                name,
+                is_module: true,
+            });
+        }
+
+        for (i, segment) in rest.iter().enumerate() {
+            let Some(found) = self.lookup(&module, segment, false, depth + 1) else {
+                // 先頭のセグメントが見つからない場合は外部クレートとみなす
+                return (i == 0 && is_relative).then_some(Resolved::External);
+            };
+            if i == rest.len() - 1 {
+                return Some(found);
+            }
+            match found {
+                Resolved::Item {
+                    module: parent,
+                    name,
+                    is_module: true,
+                } => {
+                    module = parent;
+                    module.push(name);
+                }
+                Resolved::External => return Some(Resolved::External),
+                Resolved::Item { .. } => return None,
+            }
+        }
+
+        None
+    }
+
+    /// グロブインポートの対象となるクレート内モジュールを解決する
+    fn resolve_module(
+        &self,
+        from: &[String],
+        import: &Import,
+        depth: usize,
+    ) -> Option<Vec<String>> {
+        match self.resolve_path(from, import, depth)? {
+            Resolved::Item {
+                mut module,
+                name,
+                is_module: true,
+            } => {
+                module.push(name);
+                Some(module)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::TargetKind;
+
+    fn module_file(module_path: &[&str], source: &str) -> ModuleFile {
+        ModuleFile {
+            relative_path: Default::default(),
+            module_path: module_path.iter().map(|s| s.to_string()).collect(),
+            kind: TargetKind::Lib,
+            doc_hidden: false,
+            syntax: syn::parse_str(source).unwrap(),
+        }
+    }
+
+    fn paths(api: &PublicApi, module: &[&str], name: &str) -> Vec<String> {
+        let module: Vec<String> = module.iter().map(|s| s.to_string()).collect();
+        api.public_paths(&module, name)
+            .iter()
+            .map(|p| p.join("::"))
+            .collect()
+    }
+
+    #[test]
+    fn test_public_api_reexport_from_private_module() {
+        let files = vec![
+            module_file(
+                &[],
+                r#"
+                mod inner;
+                pub mod api {
+                    pub use crate::inner::Hidden as Renamed;
+                }
+                pub use inner::Hidden;
+                "#,
+            ),
+            module_file(&["inner"], "pub struct Hidden; pub struct Unreachable;"),
+        ];
+
+        let api = PublicApi::build(&files);
+
+        assert_eq!(
+            paths(&api, &["inner"], "Hidden"),
+            vec!["Hidden", "api::Renamed"]
+        );
+        assert!(paths(&api, &["inner"], "Unreachable").is_empty());
+        assert!(!api.is_module_reachable(&["inner".to_string()]));
+        assert!(api.is_module_reachable(&["api".to_string()]));
+
+        let local_use: syn::ItemUse = syn::parse_str("pub use inner::Hidden;").unwrap();
+        assert!(api.is_local_reexport(&[], &local_use));
+    }
+
+    #[test]
+    fn test_public_api_glob_and_module_reexports() {
+        let files = vec![module_file(
+            &[],
+            r#"
+            mod private {
+                pub fn helper() {}
+                pub(crate) fn internal() {}
+                pub mod nested {
+                    pub struct Deep;
+                }
+            }
+            pub use self::private::*;
+            pub use private::nested as exposed;
+            pub use std::collections::HashMap;
+            "#,
+        )];
+
+        let api = PublicApi::build(&files);
+
+        assert_eq!(paths(&api, &["private"], "helper"), vec!["helper"]);
+        assert!(paths(&api, &["private"], "internal").is_empty());
+        assert_eq!(
+            paths(&api, &["private", "nested"], "Deep"),
+            vec!["nested::Deep", "exposed::Deep"]
+        );
+
+        let external_use: syn::ItemUse =
+            syn::parse_str("pub use std::collections::HashMap;").unwrap();
+        assert!(!api.is_local_reexport(&[], &external_use));
+    }
+}
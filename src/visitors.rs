@@ -1,72 +1,230 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 use syn::{
-    visit::Visit, ItemConst, ItemEnum, ItemExternCrate, ItemFn, ItemForeignMod, ItemImpl,
-    ItemMacro, ItemMod, ItemStatic, ItemStruct, ItemTrait, ItemTraitAlias, ItemType, ItemUnion,
-    ItemUse, Visibility,
+    punctuated::Punctuated, spanned::Spanned, visit::Visit, Attribute, Item, ItemConst, ItemEnum,
+    ItemExternCrate, ItemFn, ItemForeignMod, ItemImpl, ItemMacro, ItemMod, ItemStatic, ItemStruct,
+    ItemTrait, ItemTraitAlias, ItemType, ItemUnion, ItemUse, Visibility,
 };
 
+use crate::doctests::{is_closing_fence, is_rust_block, parse_fence, strip_hidden_lines};
+use crate::impls::{ImplBlock, ImplIndex};
+use crate::model::{ItemKind, ItemModel, Span};
+use crate::public_api::PublicApi;
+
+/// アイテムの公開パス
+pub struct ItemPath {
+    pub path: String,
+    /// 公開パスと異なる場合の定義位置
+    pub defined_at: Option<String>,
+    /// 正規のパス以外で公開されているパス
+    pub aliases: Vec<String>,
+}
+
+/// 出力するアイテムの可視性レベル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VisibilityLevel {
+    /// クレート外から利用できる公開APIのみ
+    #[default]
+    Public,
+    /// `pub(crate)` / `pub(super)` / `pub(in path)` を含むクレート内で可視なアイテム
+    Crate,
+    /// 非公開のアイテムを含むすべてのアイテム
+    All,
+}
+
+impl VisibilityLevel {
+    /// 指定された可視性のアイテムを出力対象とするかどうか
+    pub fn includes(self, vis: &Visibility) -> bool {
+        match self {
+            VisibilityLevel::Public => matches!(vis, Visibility::Public(_)),
+            VisibilityLevel::Crate => !matches!(vis, Visibility::Inherited),
+            VisibilityLevel::All => true,
+        }
+    }
+}
+
+/// 可視性をシグネチャの接頭辞として整形する（例: `pub(crate) `）
+fn format_visibility(vis: &Visibility) -> String {
+    match vis {
+        Visibility::Public(_) => "pub ".to_string(),
+        Visibility::Restricted(restricted) => {
+            let path = restricted
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+            if restricted.in_token.is_some() {
+                format!("pub(in {}) ", path)
+            } else {
+                format!("pub({}) ", path)
+            }
+        }
+        Visibility::Inherited => String::new(),
+    }
+}
+
+/// アイテムの公開パスを決定する。出力対象でない場合は None を返す
+fn resolve_item_path(
+    public_api: Option<&PublicApi>,
+    visibility: VisibilityLevel,
+    current_mod: &[String],
+    vis: &Visibility,
+    name: &str,
+) -> Option<ItemPath> {
+    let local_path = if current_mod.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}::{}", current_mod.join("::"), name)
+    };
+
+    // 内部向けの出力では再エクスポートを考慮せず定義位置で出力する
+    let public_api = public_api.filter(|_| visibility == VisibilityLevel::Public);
+
+    match public_api {
+        // 到達可能性の情報がない場合は可視性のみで判断する
+        None => visibility.includes(vis).then_some(ItemPath {
+            path: local_path,
+            defined_at: None,
+            aliases: Vec::new(),
+        }),
+        Some(api) => {
+            let paths: Vec<String> = api
+                .public_paths(current_mod, name)
+                .iter()
+                .map(|p| p.join("::"))
+                .collect();
+            let (canonical, aliases) = paths.split_first()?;
+            Some(ItemPath {
+                defined_at: (*canonical != local_path).then_some(local_path),
+                path: canonical.clone(),
+                aliases: aliases.to_vec(),
+            })
+        }
+    }
+}
+
+/// `pub use` などの宣言自体を出力対象とするかどうか
+fn is_documented_declaration(
+    public_api: Option<&PublicApi>,
+    visibility: VisibilityLevel,
+    current_mod: &[String],
+    vis: &Visibility,
+) -> bool {
+    visibility.includes(vis)
+        && (visibility != VisibilityLevel::Public
+            || public_api.is_none_or(|api| api.is_module_reachable(current_mod)))
+}
+
+/// 出力すべき `use` かどうか。公開APIの出力ではクレート内のアイテムの再エクスポートは
+/// アイテム自体が公開パスで出力されるので除外する
+fn is_documented_reexport(
+    public_api: Option<&PublicApi>,
+    visibility: VisibilityLevel,
+    current_mod: &[String],
+    node: &ItemUse,
+) -> bool {
+    is_documented_declaration(public_api, visibility, current_mod, &node.vis)
+        && !(visibility == VisibilityLevel::Public
+            && public_api.is_some_and(|api| api.is_local_reexport(current_mod, node)))
+}
+
 pub struct TocVisitor<'a> {
     pub items: &'a mut Vec<String>,
     pub current_mod: Vec<String>,
+    pub public_api: Option<&'a PublicApi>,
+    pub visibility: VisibilityLevel,
+    /// `#[doc(hidden)]` のアイテムも出力するかどうか
+    pub include_hidden: bool,
+    /// `include_str!` を解決する基準ディレクトリ（ソースファイルのディレクトリ）
+    pub source_dir: Option<PathBuf>,
+}
+
+impl<'a> TocVisitor<'a> {
+    pub fn new(items: &'a mut Vec<String>) -> Self {
+        Self {
+            items,
+            current_mod: Vec::new(),
+            public_api: None,
+            visibility: VisibilityLevel::default(),
+            include_hidden: false,
+            source_dir: None,
+        }
+    }
+
+    fn push_item(&mut self, keyword: &str, vis: &Visibility, ident: &syn::Ident) {
+        if let Some(item_path) = resolve_item_path(
+            self.public_api,
+            self.visibility,
+            &self.current_mod,
+            vis,
+            &ident.to_string(),
+        ) {
+            self.items.push(format!(
+                "{}{} {}",
+                format_visibility(vis),
+                keyword,
+                item_path.path
+            ));
+        }
+    }
+
+    fn is_documented_declaration(&self, vis: &Visibility) -> bool {
+        is_documented_declaration(self.public_api, self.visibility, &self.current_mod, vis)
+    }
 }
 
 impl<'a> Visit<'_> for TocVisitor<'a> {
+    fn visit_item(&mut self, node: &Item) {
+        if self.include_hidden || !is_doc_hidden(item_attrs(node)) {
+            syn::visit::visit_item(self, node);
+        }
+    }
+
     fn visit_item_fn(&mut self, node: &ItemFn) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub fn {}{}", mod_path, node.sig.ident));
+        let Some(proc_macro) = ProcMacro::from_attrs(&node.attrs) else {
+            self.push_item("fn", &node.vis, &node.sig.ident);
+            return;
+        };
+        if resolve_item_path(
+            self.public_api,
+            self.visibility,
+            &self.current_mod,
+            &node.vis,
+            &node.sig.ident.to_string(),
+        )
+        .is_some()
+        {
+            self.items.push(proc_macro.heading(&node.sig.ident));
         }
     }
 
     fn visit_item_struct(&mut self, node: &ItemStruct) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub struct {}{}", mod_path, node.ident));
-        }
+        self.push_item("struct", &node.vis, &node.ident);
     }
 
     fn visit_item_enum(&mut self, node: &ItemEnum) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub enum {}{}", mod_path, node.ident));
-        }
+        self.push_item("enum", &node.vis, &node.ident);
     }
 
     fn visit_item_trait(&mut self, node: &ItemTrait) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub trait {}{}", mod_path, node.ident));
-        }
+        self.push_item("trait", &node.vis, &node.ident);
     }
 
     fn visit_item_mod(&mut self, node: &ItemMod) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub mod {}{}", mod_path, node.ident));
+        let pushed = self.items.len();
+        self.push_item("mod", &node.vis, &node.ident);
+        // インラインモジュールの docs の最初の段落を添える
+        if self.items.len() > pushed && node.content.is_some() {
+            if let (Some(item), Some(summary)) = (
+                self.items.last_mut(),
+                doc_summary(&node.attrs, self.source_dir.as_deref()),
+            ) {
+                item.push_str(&format!(": {}", summary));
+            }
         }
 
         if let Some((_, items)) = &node.content {
@@ -79,161 +237,72 @@ impl<'a> Visit<'_> for TocVisitor<'a> {
     }
 
     fn visit_item_const(&mut self, node: &ItemConst) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub const {}{}", mod_path, node.ident));
-        }
+        self.push_item("const", &node.vis, &node.ident);
     }
 
     fn visit_item_static(&mut self, node: &ItemStatic) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub static {}{}", mod_path, node.ident));
-        }
+        self.push_item("static", &node.vis, &node.ident);
     }
 
     fn visit_item_type(&mut self, node: &ItemType) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub type {}{}", mod_path, node.ident));
-        }
+        self.push_item("type", &node.vis, &node.ident);
     }
 
-    fn visit_item_impl(&mut self, node: &syn::ItemImpl) {
-        // 実装対象の型名を取得
-        let impl_type = match &*node.self_ty {
-            syn::Type::Path(type_path) => type_path
-                .path
-                .segments
-                .iter()
-                .map(|s| s.ident.to_string())
-                .collect::<Vec<_>>()
-                .join("::"),
-            _ => "Unknown".to_string(),
-        };
-
-        let mod_path = if self.current_mod.is_empty() {
-            String::new()
-        } else {
-            format!("{}::", self.current_mod.join("::"))
-        };
-
-        // トレイト実装かどうか
-        if let Some((_, trait_path, _)) = &node.trait_ {
-            let trait_name = trait_path
-                .segments
-                .iter()
-                .map(|s| s.ident.to_string())
-                .collect::<Vec<_>>()
-                .join("::");
-
-            self.items
-                .push(format!("impl {} for {}{}", trait_name, mod_path, impl_type));
-        } else {
-            self.items.push(format!("impl {}{}", mod_path, impl_type));
-        }
+    fn visit_item_impl(&mut self, _node: &syn::ItemImpl) {
+        // impl ブロックは型のエントリの下に出力されるため目次には含めない
     }
 
     fn visit_item_use(&mut self, node: &ItemUse) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            let use_tree = format_use_tree(&node.tree);
-            self.items.push(format!("pub use {}{}", mod_path, use_tree));
+        if is_documented_reexport(self.public_api, self.visibility, &self.current_mod, node) {
+            self.items.push(format!(
+                "{}use {}",
+                format_visibility(&node.vis),
+                use_binding_path(&self.current_mod, &node.tree)
+            ));
         }
     }
 
     fn visit_item_macro(&mut self, node: &ItemMacro) {
         // macro_rules! マクロの場合
         if let Some(ident) = &node.ident {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items.push(format!("{}{}!", mod_path, ident));
+            if let Some(path) = macro_path(self.visibility, &self.current_mod, node, ident) {
+                self.items.push(path);
+            }
         }
     }
 
     fn visit_item_extern_crate(&mut self, node: &ItemExternCrate) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub extern crate {}{}", mod_path, node.ident));
+        if self.is_documented_declaration(&node.vis) {
+            self.items.push(format!(
+                "{}extern crate {}",
+                format_visibility(&node.vis),
+                extern_crate_binding_path(&self.current_mod, node)
+            ));
         }
     }
 
     fn visit_item_foreign_mod(&mut self, node: &ItemForeignMod) {
         for item in &node.items {
             if let syn::ForeignItem::Fn(foreign_fn) = item {
-                if matches!(foreign_fn.vis, Visibility::Public(_)) {
-                    let mod_path = if self.current_mod.is_empty() {
-                        String::new()
-                    } else {
-                        format!("{}::", self.current_mod.join("::"))
-                    };
-                    let abi = node
-                        .abi
-                        .name
-                        .as_ref()
-                        .map(|lit| lit.value())
-                        .unwrap_or("C".to_string());
-                    self.items.push(format!(
-                        "pub extern \"{}\" fn {}{}",
-                        abi, mod_path, foreign_fn.sig.ident
-                    ));
-                }
+                self.push_item(
+                    &format!("{}fn", format_foreign_mod_header(node)),
+                    &foreign_fn.vis,
+                    &foreign_fn.sig.ident,
+                );
             }
         }
     }
 
     fn visit_item_union(&mut self, node: &ItemUnion) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub union {}{}", mod_path, node.ident));
-        }
+        self.push_item("union", &node.vis, &node.ident);
     }
 
     fn visit_item_trait_alias(&mut self, node: &ItemTraitAlias) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-            self.items
-                .push(format!("pub trait {}{}", mod_path, node.ident));
-        }
+        self.push_item("trait", &node.vis, &node.ident);
     }
 }
 
+#[allow(dead_code)] // 現在はテストからのみ使用
 pub struct SummaryVisitor<'a> {
     pub public_count: &'a mut usize,
     pub types: &'a mut Vec<String>,
@@ -344,71 +413,159 @@ impl<'a> Visit<'_> for SummaryVisitor<'a> {
 pub struct CompleteDocsVisitor<'a> {
     pub content: &'a mut String,
     pub current_mod: Vec<String>,
+    pub public_api: Option<&'a PublicApi>,
+    pub visibility: VisibilityLevel,
+    /// `#[doc(hidden)]` のアイテムも出力するかどうか
+    pub include_hidden: bool,
+    /// 型ごとの impl ブロックの索引。ある場合は impl を型のエントリの下に出力する
+    pub impls: Option<&'a ImplIndex>,
+    /// `include_str!` を解決する基準ディレクトリ（ソースファイルのディレクトリ）
+    pub source_dir: Option<PathBuf>,
+    /// 見出しに表示するソースファイルのパス
+    pub source_file: PathBuf,
+    /// インラインモジュールの出力。親モジュールとは別のセクションになる
+    pub submodules: Vec<ModuleSection>,
+    /// 出力したアイテムのモデル（出力順）
+    pub items: Vec<ItemModel>,
+    /// `render_module` で出力したモジュールの docs
+    pub module_docs: Option<String>,
+    /// 見出しに添えるターゲットの表示名（例: `bin tool`）
+    pub target: Option<String>,
+}
+
+/// llms-full.txt のモジュールごとのセクション
+pub struct ModuleSection {
+    pub module_path: Vec<String>,
+    /// ターゲットの表示名（例: `bin tool`）。最初のターゲットは None
+    pub target: Option<String>,
+    pub source_file: PathBuf,
+    pub docs: Option<String>,
+    pub content: String,
+    pub items: Vec<ItemModel>,
+}
+
+/// モジュールの表示名（例: `crate`、`mod a::b`、`crate (bin tool)`）
+pub fn module_label(module_path: &[String], target: Option<&str>) -> String {
+    let label = if module_path.is_empty() {
+        "crate".to_string()
+    } else {
+        format!("mod {}", module_path.join("::"))
+    };
+    match target {
+        Some(target) => format!("{} ({})", label, target),
+        None => label,
+    }
+}
+
+/// モジュールセクションの見出しを整形する
+pub fn format_module_heading(
+    module_path: &[String],
+    target: Option<&str>,
+    source_file: &Path,
+) -> String {
+    let mut heading = format!("## {}\n\n", module_label(module_path, target));
+    if !source_file.as_os_str().is_empty() {
+        heading.push_str(&format!("*Source: `{}`*\n\n", source_file.display()));
+    }
+    heading
 }
 
 impl<'a> Visit<'_> for CompleteDocsVisitor<'a> {
-    fn visit_item_fn(&mut self, node: &ItemFn) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
+    fn visit_file(&mut self, node: &syn::File) {
+        self.render_module(&node.attrs, &node.items, true);
+
+        // インラインモジュールのセクションをモジュールパス順に続ける
+        let mut submodules = std::mem::take(&mut self.submodules);
+        submodules.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+        for section in submodules {
+            self.content.push_str(&section.content);
+        }
+    }
 
-            self.content
-                .push_str(&format!("### {}{}\n\n", mod_path, node.sig.ident));
+    fn visit_item(&mut self, node: &Item) {
+        if self.include_hidden || !is_doc_hidden(item_attrs(node)) {
+            syn::visit::visit_item(self, node);
+        }
+    }
 
-            // クリーンな関数シグネチャを作成
-            self.content.push_str("```rust\n");
+    fn visit_item_mod(&mut self, node: &ItemMod) {
+        let Some((_, items)) = &node.content else {
+            // ファイルモジュールは別ファイルとして出力される
+            return;
+        };
 
-            // Check if this is an extern "C" function
-            let is_extern_c = node
-                .sig
-                .abi
-                .as_ref()
-                .and_then(|abi| abi.name.as_ref())
-                .map(|lit| lit.value() == "C")
-                .unwrap_or(false);
-
-            if is_extern_c {
-                // Format as #[no_mangle] pub extern "C" fn ...
-                let has_no_mangle = node
-                    .attrs
-                    .iter()
-                    .any(|attr| attr.path().is_ident("no_mangle"));
+        // 公開APIの出力では非公開モジュールの概要は出さない
+        let documented = self.visibility != VisibilityLevel::Public
+            || self.item_path(&node.vis, &node.ident).is_some();
 
-                if has_no_mangle {
-                    self.content.push_str("#[no_mangle]\n");
-                }
+        let mut module_path = self.current_mod.clone();
+        module_path.push(node.ident.to_string());
 
-                let sig = format_function_signature(&node.sig, true, "");
-                // Replace "pub fn" with "pub extern \"C\" fn"
-                let extern_sig = sig.replace("pub fn", "pub extern \"C\" fn");
-                self.content.push_str(&extern_sig);
-            } else {
-                let sig = format_function_signature(&node.sig, true, "");
-                self.content.push_str(&sig);
+        // 非公開モジュールでも再エクスポートされたアイテムがあるので中身はたどる
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.current_mod = module_path.clone();
+        visitor.public_api = self.public_api;
+        visitor.visibility = self.visibility;
+        visitor.include_hidden = self.include_hidden;
+        visitor.impls = self.impls;
+        visitor.source_dir = self.source_dir.clone();
+        visitor.source_file = self.source_file.clone();
+        visitor.target = self.target.clone();
+        visitor.render_module(&node.attrs, items, documented);
+        let nested = std::mem::take(&mut visitor.submodules);
+        let docs = visitor.module_docs.take();
+        let module_items = std::mem::take(&mut visitor.items);
+
+        self.submodules.push(ModuleSection {
+            module_path,
+            target: self.target.clone(),
+            source_file: self.source_file.clone(),
+            docs,
+            content,
+            items: module_items,
+        });
+        self.submodules.extend(nested);
+    }
+
+    fn visit_item_fn(&mut self, node: &ItemFn) {
+        if let Some(proc_macro) = ProcMacro::from_attrs(&node.attrs) {
+            self.push_proc_macro(node, &proc_macro);
+            return;
+        }
+        if let Some(item_path) = self.item_path(&node.vis, &node.sig.ident) {
+            self.push_item_heading(&item_path, &node.attrs);
+
+            // クリーンな関数シグネチャを作成
+            let signature_start = self.begin_signature();
+
+            // FFI 向けの関数はシンボル名がそのまま公開されることを示す
+            let has_no_mangle = node
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("no_mangle"));
+            if has_no_mangle {
+                self.content.push_str("#[no_mangle]\n");
             }
 
-            self.content.push_str("\n```\n\n");
+            let sig = format_function_signature(&node.sig, &format_visibility(&node.vis), "");
+            self.content.push_str(&sig);
 
-            // docsコメントを抽出
-            self.extract_docs_for_item(&node.attrs);
+            self.end_entry(
+                ItemKind::Function,
+                &item_path.path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
         }
     }
 
     fn visit_item_struct(&mut self, node: &ItemStruct) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-
-            self.content
-                .push_str(&format!("### {}{}\n\n", mod_path, node.ident));
+        if let Some(item_path) = self.item_path(&node.vis, &node.ident) {
+            self.push_item_heading(&item_path, &node.attrs);
 
-            self.content.push_str("```rust\n");
+            let signature_start = self.begin_signature();
 
             // derive属性を抽出
             let derives = extract_derives(&node.attrs);
@@ -424,37 +581,33 @@ impl<'a> Visit<'_> for CompleteDocsVisitor<'a> {
                     .push_str(&format!("#[cfg({})]\n", cfg_attrs.join(", ")));
             }
 
+            for repr in format_repr_attributes(&node.attrs) {
+                self.content.push_str(&format!("{}\n", repr));
+            }
+
             // 構造体定義のクリーンな表示
-            let mut struct_def = format!("pub struct {}", node.ident);
+            let mut struct_def = format!("{}struct {}", format_visibility(&node.vis), node.ident);
 
             // ジェネリクスを追加
-            if !node.generics.params.is_empty() {
-                struct_def.push('<');
-                let generics: Vec<String> = node
-                    .generics
-                    .params
-                    .iter()
-                    .map(|p| match p {
-                        syn::GenericParam::Type(tp) => tp.ident.to_string(),
-                        syn::GenericParam::Lifetime(lp) => format!("'{}", lp.lifetime.ident),
-                        syn::GenericParam::Const(cp) => {
-                            format!("const {}: {}", cp.ident, extract_type_name(&cp.ty))
-                        }
-                    })
-                    .collect();
-                struct_def.push_str(&generics.join(", "));
-                struct_def.push('>');
-            }
+            struct_def.push_str(&format_generics(&node.generics));
+            let where_clause = format_where_clause(&node.generics, "");
 
             // フィールドを表示
             match &node.fields {
                 syn::Fields::Named(fields) => {
-                    struct_def.push_str(" {\n");
+                    struct_def.push_str(&where_clause);
+                    struct_def.push_str(open_brace(&where_clause));
                     for field in &fields.named {
                         if let Some(ident) = &field.ident {
-                            if matches!(field.vis, Visibility::Public(_)) {
+                            if self.is_documented_member(&field.vis, &field.attrs) {
+                                struct_def.push_str(&format_doc_comment(
+                                    &field.attrs,
+                                    self.source_dir.as_deref(),
+                                    "    ",
+                                ));
                                 struct_def.push_str(&format!(
-                                    "    pub {}: {},\n",
+                                    "    {}{}: {},\n",
+                                    format_visibility(&field.vis),
                                     ident,
                                     extract_type_name(&field.ty)
                                 ));
@@ -464,40 +617,42 @@ impl<'a> Visit<'_> for CompleteDocsVisitor<'a> {
                     struct_def.push('}');
                 }
                 syn::Fields::Unnamed(fields) => {
-                    struct_def.push('(');
-                    let field_types: Vec<String> = fields
+                    let fields: Vec<&syn::Field> = fields
                         .unnamed
                         .iter()
-                        .filter(|f| matches!(f.vis, Visibility::Public(_)))
-                        .map(|f| format!("pub {}", extract_type_name(&f.ty)))
+                        .filter(|f| self.is_documented_member(&f.vis, &f.attrs))
                         .collect();
-                    struct_def.push_str(&field_types.join(", "));
-                    struct_def.push_str(");");
+                    struct_def.push_str(&format_tuple_fields(
+                        &fields,
+                        self.source_dir.as_deref(),
+                        "",
+                    ));
+                    struct_def.push_str(&where_clause);
+                    struct_def.push(';');
                 }
                 syn::Fields::Unit => {
+                    struct_def.push_str(&where_clause);
                     struct_def.push(';');
                 }
             }
 
             self.content.push_str(&struct_def);
-            self.content.push_str("\n```\n\n");
-
-            self.extract_docs_for_item(&node.attrs);
+            self.end_entry(
+                ItemKind::Struct,
+                &item_path.path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
+            self.push_type_impls(&item_path, &node.ident, &node.generics, &node.attrs);
         }
     }
 
     fn visit_item_enum(&mut self, node: &ItemEnum) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
+        if let Some(item_path) = self.item_path(&node.vis, &node.ident) {
+            self.push_item_heading(&item_path, &node.attrs);
 
-            self.content
-                .push_str(&format!("### {}{}\n\n", mod_path, node.ident));
-
-            self.content.push_str("```rust\n");
+            let signature_start = self.begin_signature();
 
             // derive属性を抽出
             let derives = extract_derives(&node.attrs);
@@ -513,32 +668,24 @@ impl<'a> Visit<'_> for CompleteDocsVisitor<'a> {
                     .push_str(&format!("#[cfg({})]\n", cfg_attrs.join(", ")));
             }
 
+            for repr in format_repr_attributes(&node.attrs) {
+                self.content.push_str(&format!("{}\n", repr));
+            }
+
             // 列挙型定義のクリーンな表示
-            let mut enum_def = format!("pub enum {}", node.ident);
+            let mut enum_def = format!("{}enum {}", format_visibility(&node.vis), node.ident);
 
             // ジェネリクスを追加
-            if !node.generics.params.is_empty() {
-                enum_def.push('<');
-                let generics: Vec<String> = node
-                    .generics
-                    .params
-                    .iter()
-                    .map(|p| match p {
-                        syn::GenericParam::Type(tp) => tp.ident.to_string(),
-                        syn::GenericParam::Lifetime(lp) => format!("'{}", lp.lifetime.ident),
-                        syn::GenericParam::Const(cp) => {
-                            format!("const {}: {}", cp.ident, extract_type_name(&cp.ty))
-                        }
-                    })
-                    .collect();
-                enum_def.push_str(&generics.join(", "));
-                enum_def.push('>');
-            }
-
-            enum_def.push_str(" {\n");
+            enum_def.push_str(&format_generics(&node.generics));
+            let where_clause = format_where_clause(&node.generics, "");
+            enum_def.push_str(&where_clause);
+            enum_def.push_str(open_brace(&where_clause));
 
             // バリアントを表示
             for variant in &node.variants {
+                if !self.include_hidden && is_doc_hidden(&variant.attrs) {
+                    continue;
+                }
                 let mut variant_str = format!("    {}", variant.ident);
 
                 // cfg属性を抽出
@@ -553,25 +700,45 @@ impl<'a> Visit<'_> for CompleteDocsVisitor<'a> {
 
                 // バリアントフィールドを表示
                 match &variant.fields {
+                    syn::Fields::Named(fields)
+                        if fields
+                            .named
+                            .iter()
+                            .any(|f| has_doc_lines(&f.attrs, self.source_dir.as_deref())) =>
+                    {
+                        // docs のあるフィールドは 1 行に 1 フィールドで出力する
+                        variant_str.push_str(" {\n");
+                        for f in &fields.named {
+                            if let Some(ident) = &f.ident {
+                                variant_str.push_str(&format_doc_comment(
+                                    &f.attrs,
+                                    self.source_dir.as_deref(),
+                                    "        ",
+                                ));
+                                variant_str.push_str(&format!(
+                                    "        {}{}: {},\n",
+                                    format_visibility(&f.vis),
+                                    ident,
+                                    extract_type_name(&f.ty)
+                                ));
+                            }
+                        }
+                        variant_str.push_str("    }");
+                    }
                     syn::Fields::Named(fields) => {
                         variant_str.push_str(" {");
                         let field_strs: Vec<String> = fields
                             .named
                             .iter()
                             .filter_map(|f| {
-                                if let Some(ident) = &f.ident {
-                                    if matches!(f.vis, syn::Visibility::Public(_)) {
-                                        Some(format!(
-                                            " pub {}: {}",
-                                            ident,
-                                            extract_type_name(&f.ty)
-                                        ))
-                                    } else {
-                                        Some(format!(" {}: {}", ident, extract_type_name(&f.ty)))
-                                    }
-                                } else {
-                                    None
-                                }
+                                f.ident.as_ref().map(|ident| {
+                                    format!(
+                                        " {}{}: {}",
+                                        format_visibility(&f.vis),
+                                        ident,
+                                        extract_type_name(&f.ty)
+                                    )
+                                })
                             })
                             .collect();
                         if !field_strs.is_empty() {
@@ -581,103 +748,108 @@ impl<'a> Visit<'_> for CompleteDocsVisitor<'a> {
                         variant_str.push('}');
                     }
                     syn::Fields::Unnamed(fields) => {
-                        variant_str.push('(');
-                        let field_types: Vec<String> = fields
-                            .unnamed
-                            .iter()
-                            .map(|f| extract_type_name(&f.ty))
-                            .collect();
-                        if field_types.is_empty() || field_types.iter().all(|t| t == "Unknown") {
-                            variant_str.push_str("..");
-                        } else {
-                            variant_str.push_str(&field_types.join(", "));
-                        }
-                        variant_str.push(')');
+                        let fields: Vec<&syn::Field> = fields.unnamed.iter().collect();
+                        variant_str.push_str(&format_tuple_fields(
+                            &fields,
+                            self.source_dir.as_deref(),
+                            "    ",
+                        ));
                     }
                     syn::Fields::Unit => {
                         // Unit variant, no additional fields
                     }
                 }
 
+                // 明示的な判別値
+                if let Some((_, discriminant)) = &variant.discriminant {
+                    variant_str.push_str(&format!(" = {}", format_expr(discriminant)));
+                }
+
+                enum_def.push_str(&format_doc_comment(
+                    &variant.attrs,
+                    self.source_dir.as_deref(),
+                    "    ",
+                ));
                 enum_def.push_str(&format!("{},\n", variant_str));
             }
 
             enum_def.push('}');
 
             self.content.push_str(&enum_def);
-            self.content.push_str("\n```\n\n");
-
-            self.extract_docs_for_item(&node.attrs);
+            self.end_entry(
+                ItemKind::Enum,
+                &item_path.path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
+            self.push_type_impls(&item_path, &node.ident, &node.generics, &node.attrs);
         }
     }
 
     fn visit_item_trait(&mut self, node: &ItemTrait) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-
-            self.content
-                .push_str(&format!("### {}{}\n\n", mod_path, node.ident));
+        if let Some(item_path) = self.item_path(&node.vis, &node.ident) {
+            self.push_item_heading(&item_path, &node.attrs);
 
-            self.content.push_str("```rust\n");
+            let signature_start = self.begin_signature();
             // トレイト定義のクリーンな表示
-            let mut trait_signature = format!("pub trait {}", node.ident);
+            let mut trait_signature = format!(
+                "{}{}{}trait {}",
+                format_visibility(&node.vis),
+                if node.unsafety.is_some() {
+                    "unsafe "
+                } else {
+                    ""
+                },
+                if node.auto_token.is_some() {
+                    "auto "
+                } else {
+                    ""
+                },
+                node.ident
+            );
 
             // ジェネリクスを追加
-            if !node.generics.params.is_empty() {
-                trait_signature.push('<');
-                let generics: Vec<String> = node
-                    .generics
-                    .params
-                    .iter()
-                    .map(|p| match p {
-                        syn::GenericParam::Type(tp) => tp.ident.to_string(),
-                        syn::GenericParam::Lifetime(lp) => format!("'{}", lp.lifetime.ident),
-                        syn::GenericParam::Const(cp) => {
-                            format!("const {}: {}", cp.ident, extract_type_name(&cp.ty))
-                        }
-                    })
-                    .collect();
-                trait_signature.push_str(&generics.join(", "));
-                trait_signature.push('>');
-            }
+            trait_signature.push_str(&format_generics(&node.generics));
 
             // Super traitsを追加
             if !node.supertraits.is_empty() {
                 trait_signature.push_str(": ");
-                let supertraits: Vec<String> = node
-                    .supertraits
-                    .iter()
-                    .map(|st| match st {
-                        syn::TypeParamBound::Trait(trait_bound) => trait_bound
-                            .path
-                            .segments
-                            .iter()
-                            .map(|s| s.ident.to_string())
-                            .collect::<Vec<_>>()
-                            .join("::"),
-                        syn::TypeParamBound::Lifetime(lifetime) => lifetime.ident.to_string(),
-                        _ => "Unknown".to_string(),
-                    })
-                    .collect();
-                trait_signature.push_str(&supertraits.join(" + "));
+                trait_signature.push_str(&format_trait_bounds(&node.supertraits));
             }
 
-            trait_signature.push_str(" {");
+            let where_clause = format_where_clause(&node.generics, "");
+            trait_signature.push_str(&where_clause);
+            trait_signature.push_str(open_brace(&where_clause).trim_end());
             self.content.push_str(&trait_signature);
 
             // トレイトアイテムを完全なシグネチャで表示
             for item in &node.items {
+                let attrs = match item {
+                    syn::TraitItem::Fn(method) => &method.attrs,
+                    syn::TraitItem::Type(ty) => &ty.attrs,
+                    syn::TraitItem::Const(c) => &c.attrs,
+                    _ => continue,
+                };
+                if !self.include_hidden && is_doc_hidden(attrs) {
+                    continue;
+                }
                 match item {
                     syn::TraitItem::Fn(method) => {
-                        let sig = format_function_signature(&method.sig, false, "    ");
+                        let sig = format_function_signature(&method.sig, "", "    ");
                         self.content.push_str(&format!("\n    {};", sig));
                     }
                     syn::TraitItem::Type(ty) => {
-                        self.content.push_str(&format!("\n    type {};", ty.ident));
+                        let mut type_def =
+                            format!("type {}{}", ty.ident, format_generics(&ty.generics));
+                        if !ty.bounds.is_empty() {
+                            type_def.push_str(&format!(": {}", format_trait_bounds(&ty.bounds)));
+                        }
+                        if let Some((_, default)) = &ty.default {
+                            type_def.push_str(&format!(" = {}", extract_type_name(default)));
+                        }
+                        type_def.push_str(&format_where_clause(&ty.generics, "    "));
+                        self.content.push_str(&format!("\n    {};", type_def));
                     }
                     syn::TraitItem::Const(c) => {
                         // クリーンなconst定義
@@ -689,283 +861,225 @@ impl<'a> Visit<'_> for CompleteDocsVisitor<'a> {
                 }
             }
 
-            self.content.push_str("\n}\n```\n\n");
-
-            self.extract_docs_for_item(&node.attrs);
+            self.content.push_str("\n}");
+            self.end_entry(
+                ItemKind::Trait,
+                &item_path.path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
         }
     }
 
     fn visit_item_const(&mut self, node: &ItemConst) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
+        if let Some(item_path) = self.item_path(&node.vis, &node.ident) {
+            self.push_item_heading(&item_path, &node.attrs);
 
-            self.content
-                .push_str(&format!("### {}{}\n\n", mod_path, node.ident));
-
-            self.content.push_str("```rust\n");
+            let signature_start = self.begin_signature();
             // クリーンなconst定義
             self.content.push_str(&format!(
-                "pub const {}: {}",
+                "{}const {}: {}",
+                format_visibility(&node.vis),
                 node.ident,
                 extract_type_name(&node.ty)
             ));
-            self.content.push_str("\n```\n\n");
-
-            self.extract_docs_for_item(&node.attrs);
+            self.end_entry(
+                ItemKind::Const,
+                &item_path.path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
         }
     }
 
     fn visit_item_static(&mut self, node: &ItemStatic) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
-
-            self.content
-                .push_str(&format!("### {}{}\n\n", mod_path, node.ident));
+        if let Some(item_path) = self.item_path(&node.vis, &node.ident) {
+            self.push_item_heading(&item_path, &node.attrs);
 
-            self.content.push_str("```rust\n");
+            let signature_start = self.begin_signature();
             // クリーンなstatic定義
             let mut static_def = String::new();
-            static_def.push_str("pub static ");
+            static_def.push_str(&format_visibility(&node.vis));
+            static_def.push_str("static ");
             if matches!(node.mutability, syn::StaticMutability::Mut(_)) {
                 static_def.push_str("mut ");
             }
             static_def.push_str(&format!("{}: {}", node.ident, extract_type_name(&node.ty)));
             self.content.push_str(&static_def);
-            self.content.push_str("\n```\n\n");
-
-            self.extract_docs_for_item(&node.attrs);
+            self.end_entry(
+                ItemKind::Static,
+                &item_path.path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
         }
     }
 
     fn visit_item_type(&mut self, node: &ItemType) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let mod_path = if self.current_mod.is_empty() {
-                String::new()
-            } else {
-                format!("{}::", self.current_mod.join("::"))
-            };
+        if let Some(item_path) = self.item_path(&node.vis, &node.ident) {
+            self.push_item_heading(&item_path, &node.attrs);
 
-            self.content
-                .push_str(&format!("### {}{}\n\n", mod_path, node.ident));
-
-            self.content.push_str("```rust\n");
+            let signature_start = self.begin_signature();
             // クリーンなtype alias定義
-            let mut type_def = format!("pub type {}", node.ident);
+            let mut type_def = format!("{}type {}", format_visibility(&node.vis), node.ident);
 
             // ジェネリクスを追加
-            if !node.generics.params.is_empty() {
-                let generics: Vec<String> = node
-                    .generics
-                    .params
-                    .iter()
-                    .map(|p| match p {
-                        syn::GenericParam::Type(tp) => tp.ident.to_string(),
-                        syn::GenericParam::Lifetime(lp) => format!("'{}", lp.lifetime.ident),
-                        syn::GenericParam::Const(cp) => {
-                            format!("const {}: {}", cp.ident, extract_type_name(&cp.ty))
-                        }
-                    })
-                    .collect();
-                type_def.push_str(&format!("<{}>", generics.join(", ")));
-            }
+            type_def.push_str(&format_generics(&node.generics));
 
             type_def.push_str(&format!(" = {}", extract_type_name(&node.ty)));
 
             // where句
-            if let Some(where_clause) = &node.generics.where_clause {
-                if !where_clause.predicates.is_empty() {
-                    type_def.push_str("\nwhere\n    ");
-                    type_def.push_str(&extract_where_clause(where_clause));
-                }
-            }
+            type_def.push_str(&format_where_clause(&node.generics, ""));
 
             self.content.push_str(&type_def);
-            self.content.push_str("\n```\n\n");
-
-            self.extract_docs_for_item(&node.attrs);
+            self.end_entry(
+                ItemKind::TypeAlias,
+                &item_path.path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
         }
     }
 
     fn visit_item_impl(&mut self, node: &ItemImpl) {
-        // 実装対象の型名を取得
-        let impl_type = match &*node.self_ty {
-            syn::Type::Path(type_path) => type_path
-                .path
-                .segments
-                .iter()
-                .map(|s| s.ident.to_string())
-                .collect::<Vec<_>>()
-                .join("::"),
-            _ => "Unknown".to_string(),
-        };
+        // クレート内の型への impl は型のエントリの下に出力される
+        if self.impls.is_some_and(|index| {
+            index
+                .resolve_self_type(&self.current_mod, &node.self_ty)
+                .is_some()
+        }) {
+            return;
+        }
 
         let mod_path = if self.current_mod.is_empty() {
             String::new()
         } else {
             format!("{}::", self.current_mod.join("::"))
         };
-
-        // トレイト実装かどうか
-        if let Some((_, trait_path, _)) = &node.trait_ {
-            let trait_name = extract_path_with_generics(trait_path);
-
-            self.content.push_str(&format!(
-                "### impl {} for {}{}\n\n",
-                trait_name, mod_path, impl_type
-            ));
-        } else {
-            self.content
-                .push_str(&format!("### impl {}{}\n\n", mod_path, impl_type));
-        }
-
-        self.content.push_str("```rust\n");
-
-        // impl シグネチャを構築
-        let mut impl_sig = String::new();
-
-        // ジェネリクス
-        if !node.generics.params.is_empty() {
-            impl_sig.push_str("impl<");
-            let generics: Vec<String> = node
-                .generics
-                .params
-                .iter()
-                .map(|p| match p {
-                    syn::GenericParam::Type(tp) => tp.ident.to_string(),
-                    syn::GenericParam::Lifetime(lp) => format!("'{}", lp.lifetime.ident),
-                    syn::GenericParam::Const(cp) => {
-                        format!("const {}: {}", cp.ident, extract_type_name(&cp.ty))
-                    }
-                })
-                .collect();
-            impl_sig.push_str(&generics.join(", "));
-            impl_sig.push_str("> ");
-        } else {
-            impl_sig.push_str("impl ");
-        }
-
-        // トレイト実装の場合
-        if let Some((_, trait_path, _)) = &node.trait_ {
-            let trait_name = extract_path_with_generics(trait_path);
-            impl_sig.push_str(&format!("{} for ", trait_name));
-        }
-
-        impl_sig.push_str(&impl_type);
-        impl_sig.push_str(" {");
-
-        self.content.push_str(&impl_sig);
-
-        // public メソッドを表示
-        for item in &node.items {
-            match item {
-                syn::ImplItem::Fn(method) => {
-                    if matches!(method.vis, Visibility::Public(_)) {
-                        let sig = format_function_signature(&method.sig, true, "    ");
-                        self.content.push_str(&format!("\n    {};", sig));
-                    }
-                }
-                syn::ImplItem::Const(const_item) => {
-                    if matches!(const_item.vis, Visibility::Public(_)) {
-                        self.content
-                            .push_str(&format!("\n    pub const {}: Type;", const_item.ident));
-                    }
-                }
-                syn::ImplItem::Type(type_item) => {
-                    if matches!(type_item.vis, Visibility::Public(_)) {
-                        self.content
-                            .push_str(&format!("\n    pub type {};", type_item.ident));
-                    }
-                }
-                _ => {}
-            }
-        }
-
-        self.content.push_str("\n}\n```\n\n");
-
-        // impl ブロックのdocsコメントがあれば抽出
-        self.extract_docs_for_item(&node.attrs);
+        let owner = format!("{}{}", mod_path, extract_type_name(&node.self_ty));
+        self.render_impl_block(node, "###", &owner);
     }
 
     fn visit_item_use(&mut self, node: &ItemUse) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            let use_tree = format_use_tree(&node.tree);
-
-            self.content.push_str(&format!("### {}\n\n", use_tree));
-            self.content.push_str("```rust\n");
-            self.content.push_str(&format!("pub use {};\n", use_tree));
-            self.content.push_str("```\n\n");
+        if is_documented_reexport(self.public_api, self.visibility, &self.current_mod, node) {
+            let path = use_binding_path(&self.current_mod, &node.tree);
 
-            self.extract_docs_for_item(&node.attrs);
+            self.content.push_str(&format!("### {}\n\n", path));
+            self.push_doc_cfg_badge(&node.attrs);
+            let signature_start = self.begin_signature();
+            self.content.push_str(&format!(
+                "{}use {};\n",
+                format_visibility(&node.vis),
+                format_use_tree(&node.tree)
+            ));
+            self.end_entry(
+                ItemKind::ReExport,
+                &path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
         }
     }
 
     fn visit_item_macro(&mut self, node: &ItemMacro) {
-        if let Some(ident) = &node.ident {
-            self.content.push_str(&format!("### {}!\n\n", ident));
-            self.content.push_str("```rust\n");
-            self.content.push_str(&format!(
-                "macro_rules! {} {{\n    // macro definition\n}}\n",
-                ident
-            ));
-            self.content.push_str("```\n\n");
+        let Some(ident) = &node.ident else {
+            return;
+        };
+        let Some(path) = macro_path(self.visibility, &self.current_mod, node, ident) else {
+            return;
+        };
 
-            self.extract_docs_for_item(&node.attrs);
+        self.content.push_str(&format!("### {}\n\n", path));
+        self.push_doc_cfg_badge(&node.attrs);
+        let signature_start = self.begin_signature();
+        if is_macro_exported(&node.attrs) {
+            self.content.push_str("#[macro_export]\n");
         }
+        self.content
+            .push_str(&format_macro_rules(ident, &node.mac.tokens));
+        self.end_entry(
+            ItemKind::Macro,
+            &path,
+            signature_start,
+            &node.attrs,
+            node.span(),
+        );
     }
 
     fn visit_item_extern_crate(&mut self, node: &ItemExternCrate) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            self.content
-                .push_str(&format!("### extern crate {}\n\n", node.ident));
-            self.content.push_str("```rust\n");
-            self.content
-                .push_str(&format!("pub extern crate {};\n", node.ident));
-            self.content.push_str("```\n\n");
-
-            self.extract_docs_for_item(&node.attrs);
+        if is_documented_declaration(
+            self.public_api,
+            self.visibility,
+            &self.current_mod,
+            &node.vis,
+        ) {
+            let path = extern_crate_binding_path(&self.current_mod, node);
+
+            self.content.push_str(&format!("### {}\n\n", path));
+            self.push_doc_cfg_badge(&node.attrs);
+            let signature_start = self.begin_signature();
+            let rename = node
+                .rename
+                .as_ref()
+                .map(|(_, rename)| format!(" as {}", rename))
+                .unwrap_or_default();
+            self.content.push_str(&format!(
+                "{}extern crate {}{};\n",
+                format_visibility(&node.vis),
+                node.ident,
+                rename
+            ));
+            self.end_entry(
+                ItemKind::ExternCrate,
+                &path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
         }
     }
 
     fn visit_item_foreign_mod(&mut self, node: &ItemForeignMod) {
         for item in &node.items {
             if let syn::ForeignItem::Fn(foreign_fn) = item {
-                if matches!(foreign_fn.vis, Visibility::Public(_)) {
-                    let abi = node
-                        .abi
-                        .name
-                        .as_ref()
-                        .map(|lit| lit.value())
-                        .unwrap_or("C".to_string());
-
-                    self.content
-                        .push_str(&format!("### {}\n\n", foreign_fn.sig.ident));
-                    self.content.push_str("```rust\n");
+                if !self.include_hidden && is_doc_hidden(&foreign_fn.attrs) {
+                    continue;
+                }
+                if let Some(item_path) = self.item_path(&foreign_fn.vis, &foreign_fn.sig.ident) {
+                    self.push_item_heading(&item_path, &foreign_fn.attrs);
+                    let signature_start = self.begin_signature();
 
                     // Format as extern "ABI" { pub fn ... }
-                    self.content.push_str(&format!("extern \"{}\" {{\n", abi));
-                    let sig = format_function_signature(&foreign_fn.sig, true, "");
+                    self.content
+                        .push_str(&format!("{}{{\n", format_foreign_mod_header(node)));
+                    let sig = format_function_signature(
+                        &foreign_fn.sig,
+                        &format_visibility(&foreign_fn.vis),
+                        "",
+                    );
                     self.content.push_str(&format!("    {};\n", sig));
                     self.content.push_str("}\n");
-                    self.content.push_str("```\n\n");
-
-                    self.extract_docs_for_item(&foreign_fn.attrs);
+                    self.end_entry(
+                        ItemKind::ForeignFunction,
+                        &item_path.path,
+                        signature_start,
+                        &foreign_fn.attrs,
+                        foreign_fn.span(),
+                    );
                 }
             }
         }
     }
 
     fn visit_item_union(&mut self, node: &ItemUnion) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            self.content.push_str(&format!("### {}\n\n", node.ident));
-            self.content.push_str("```rust\n");
+        if let Some(item_path) = self.item_path(&node.vis, &node.ident) {
+            self.push_item_heading(&item_path, &node.attrs);
+            let signature_start = self.begin_signature();
 
             // Extract and format attributes
             let attrs = extract_cfg_attributes(&node.attrs);
@@ -973,60 +1087,302 @@ impl<'a> Visit<'_> for CompleteDocsVisitor<'a> {
                 self.content.push_str(&format!("#[{}]\n", attrs.join(", ")));
             }
 
-            // Union header with generics
-            let mut union_def = format!("pub union {}", node.ident);
-            if !node.generics.params.is_empty() {
-                let generics = format_generic_params_simple(&node.generics.params);
-                union_def.push_str(&format!("<{}>", generics));
+            for repr in format_repr_attributes(&node.attrs) {
+                self.content.push_str(&format!("{}\n", repr));
             }
 
-            self.content.push_str(&format!("{} {{\n", union_def));
+            // Union header with generics
+            let mut union_def = format!("{}union {}", format_visibility(&node.vis), node.ident);
+            union_def.push_str(&format_generics(&node.generics));
+            let where_clause = format_where_clause(&node.generics, "");
+            union_def.push_str(&where_clause);
+            union_def.push_str(open_brace(&where_clause));
+
+            self.content.push_str(&union_def);
 
             // Union fields
             for field in &node.fields.named {
                 if let Some(ident) = &field.ident {
-                    let type_str = extract_type_name(&field.ty);
-                    self.content
-                        .push_str(&format!("    pub {}: {},\n", ident, type_str));
+                    if self.is_documented_member(&field.vis, &field.attrs) {
+                        self.content.push_str(&format_doc_comment(
+                            &field.attrs,
+                            self.source_dir.as_deref(),
+                            "    ",
+                        ));
+                        let type_str = extract_type_name(&field.ty);
+                        self.content.push_str(&format!(
+                            "    {}{}: {},\n",
+                            format_visibility(&field.vis),
+                            ident,
+                            type_str
+                        ));
+                    }
                 }
             }
 
-            self.content.push_str("}\n```\n\n");
-
-            self.extract_docs_for_item(&node.attrs);
+            self.content.push('}');
+            self.end_entry(
+                ItemKind::Union,
+                &item_path.path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
+            self.push_type_impls(&item_path, &node.ident, &node.generics, &node.attrs);
         }
     }
 
     fn visit_item_trait_alias(&mut self, node: &ItemTraitAlias) {
-        if matches!(node.vis, Visibility::Public(_)) {
-            self.content.push_str(&format!("### {}\n\n", node.ident));
-            self.content.push_str("```rust\n");
+        if let Some(item_path) = self.item_path(&node.vis, &node.ident) {
+            self.push_item_heading(&item_path, &node.attrs);
+            let signature_start = self.begin_signature();
 
             // Trait alias with generics
-            let mut trait_alias = format!("pub trait {}", node.ident);
-            if !node.generics.params.is_empty() {
-                let generics = format_generic_params_simple(&node.generics.params);
-                trait_alias.push_str(&format!("<{}>", generics));
-            }
+            let mut trait_alias = format!("{}trait {}", format_visibility(&node.vis), node.ident);
+            trait_alias.push_str(&format_generics(&node.generics));
 
             // Format bounds
             let bounds = format_trait_bounds(&node.bounds);
             trait_alias.push_str(&format!(" = {}", bounds));
 
             // Add where clause if present
-            if let Some(where_clause) = &node.generics.where_clause {
-                let where_str = extract_where_clause(where_clause);
-                if !where_str.is_empty() {
-                    trait_alias.push_str(&format!("\nwhere\n    {}", where_str));
+            trait_alias.push_str(&format_where_clause(&node.generics, ""));
+
+            self.content.push_str(&format!("{};\n", trait_alias));
+            self.end_entry(
+                ItemKind::TraitAlias,
+                &item_path.path,
+                signature_start,
+                &node.attrs,
+                node.span(),
+            );
+        }
+    }
+}
+
+/// 手続き的マクロの種類
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ProcMacro {
+    /// `#[proc_macro]`
+    FunctionLike,
+    /// `#[proc_macro_attribute]`
+    Attribute,
+    /// `#[proc_macro_derive(Name, attributes(helper))]`
+    Derive { name: String, helpers: Vec<String> },
+}
+
+impl ProcMacro {
+    /// 関数の属性から手続き的マクロの種類を判定する
+    fn from_attrs(attrs: &[Attribute]) -> Option<Self> {
+        attrs.iter().find_map(|attr| {
+            let path = attr.path();
+            if path.is_ident("proc_macro") {
+                Some(ProcMacro::FunctionLike)
+            } else if path.is_ident("proc_macro_attribute") {
+                Some(ProcMacro::Attribute)
+            } else if path.is_ident("proc_macro_derive") {
+                parse_proc_macro_derive(attr)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// モデルに記録するパス。手続き的マクロはクレートルートから公開される
+    fn path(&self, ident: &syn::Ident) -> String {
+        match self {
+            ProcMacro::FunctionLike => format!("{}!", ident),
+            ProcMacro::Attribute => ident.to_string(),
+            ProcMacro::Derive { name, .. } => name.clone(),
+        }
+    }
+
+    /// 見出しと目次に使う呼び出し方（例: `#[derive(Name)]`）
+    fn heading(&self, ident: &syn::Ident) -> String {
+        match self {
+            ProcMacro::FunctionLike => format!("{}!", ident),
+            ProcMacro::Attribute => format!("#[{}]", ident),
+            ProcMacro::Derive { name, .. } => format!("#[derive({})]", name),
+        }
+    }
+
+    /// rustdoc と同じく、関数のシグネチャではなく呼び出し方を示す
+    fn usage(&self, ident: &syn::Ident) -> String {
+        match self {
+            ProcMacro::FunctionLike => format!("{}!(...)", ident),
+            ProcMacro::Attribute => format!("#[{}(...)]", ident),
+            ProcMacro::Derive { name, helpers } if helpers.is_empty() => {
+                format!("#[derive({})]", name)
+            }
+            ProcMacro::Derive { name, helpers } => {
+                let mut usage = format!(
+                    "#[derive({})]\n{{\n    // Attributes available to this derive:\n",
+                    name
+                );
+                for helper in helpers {
+                    usage.push_str(&format!("    #[{}]\n", helper));
+                }
+                usage.push('}');
+                usage
+            }
+        }
+    }
+
+    fn kind(&self) -> ItemKind {
+        match self {
+            ProcMacro::FunctionLike => ItemKind::ProcMacro,
+            ProcMacro::Attribute => ItemKind::AttributeMacro,
+            ProcMacro::Derive { .. } => ItemKind::DeriveMacro,
+        }
+    }
+}
+
+/// `#[proc_macro_derive(Name, attributes(a, b))]` から derive 名とヘルパー属性を取り出す
+fn parse_proc_macro_derive(attr: &Attribute) -> Option<ProcMacro> {
+    let args = attr
+        .parse_args_with(Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)
+        .ok()?;
+    let mut name = None;
+    let mut helpers = Vec::new();
+    for meta in args {
+        match meta {
+            syn::Meta::Path(path) if name.is_none() => {
+                name = Some(extract_path_with_generics(&path));
+            }
+            syn::Meta::List(list) if list.path.is_ident("attributes") => {
+                if let Ok(paths) =
+                    list.parse_args_with(Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)
+                {
+                    helpers.extend(paths.iter().map(extract_path_with_generics));
                 }
             }
+            _ => {}
+        }
+    }
+    Some(ProcMacro::Derive {
+        name: name?,
+        helpers,
+    })
+}
 
-            self.content.push_str(&format!("{};\n", trait_alias));
-            self.content.push_str("```\n\n");
+/// `#[macro_export]` が付いているかどうか
+fn is_macro_exported(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("macro_export"))
+}
 
-            self.extract_docs_for_item(&node.attrs);
+/// 出力する `macro_rules!` マクロのパス（例: `my_macro!`）。`#[macro_export]` のマクロは
+/// クレートルートから公開される。それ以外はクレート内でのみ使えるので公開APIの出力では除外する
+fn macro_path(
+    visibility: VisibilityLevel,
+    current_mod: &[String],
+    node: &ItemMacro,
+    ident: &syn::Ident,
+) -> Option<String> {
+    if is_macro_exported(&node.attrs) {
+        return Some(format!("{}!", ident));
+    }
+    if visibility == VisibilityLevel::Public {
+        return None;
+    }
+    let mut path = current_mod.to_vec();
+    path.push(format!("{}!", ident));
+    Some(path.join("::"))
+}
+
+/// rustdoc と同じく、各ルールのマッチャーを残して展開部分を `{ ... }` に置き換える
+fn format_macro_rules(ident: &syn::Ident, body: &proc_macro2::TokenStream) -> String {
+    let mut result = format!("macro_rules! {} {{\n", ident);
+    let mut tokens = body.clone().into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        // ルールは `(matcher) => { transcriber };` の形
+        let proc_macro2::TokenTree::Group(matcher) = token else {
+            continue;
+        };
+        let (open, close) = delimiters(matcher.delimiter());
+        result.push_str(&format!(
+            "    {}{}{} => {{ ... }};\n",
+            open,
+            format_macro_tokens(matcher.stream()),
+            close
+        ));
+        // `=>`、展開部分、`;` を読み飛ばす
+        for token in tokens.by_ref() {
+            if matches!(&token, proc_macro2::TokenTree::Group(_)) {
+                break;
+            }
+        }
+        if matches!(tokens.peek(), Some(proc_macro2::TokenTree::Punct(p)) if p.as_char() == ';') {
+            tokens.next();
+        }
+    }
+    result.push('}');
+    result
+}
+
+fn delimiters(delimiter: proc_macro2::Delimiter) -> (&'static str, &'static str) {
+    match delimiter {
+        proc_macro2::Delimiter::Parenthesis => ("(", ")"),
+        proc_macro2::Delimiter::Bracket => ("[", "]"),
+        proc_macro2::Delimiter::Brace => ("{", "}"),
+        proc_macro2::Delimiter::None => ("", ""),
+    }
+}
+
+/// マッチャーのトークン列を `$name:ident, $($arg:expr),*` のように整形する
+fn format_macro_tokens(stream: proc_macro2::TokenStream) -> String {
+    use proc_macro2::{Spacing, TokenTree};
+
+    let tokens: Vec<TokenTree> = stream.into_iter().collect();
+    let mut result = String::new();
+    // 直前のトークンとの間に空白を入れないかどうか
+    let mut glue = true;
+    // `$(...)` の直後の区切り文字と繰り返し演算子
+    let mut after_repetition = false;
+    for (i, token) in tokens.iter().enumerate() {
+        let prev_dollar =
+            i > 0 && matches!(&tokens[i - 1], TokenTree::Punct(p) if p.as_char() == '$');
+        let text = match token {
+            TokenTree::Group(group) => {
+                let (open, close) = delimiters(group.delimiter());
+                format!("{}{}{}", open, format_macro_tokens(group.stream()), close)
+            }
+            other => other.to_string(),
+        };
+        let is_punct = |c: char| matches!(token, TokenTree::Punct(p) if p.as_char() == c);
+
+        let no_space = glue
+            || is_punct(',')
+            || is_punct(';')
+            || (after_repetition && matches!(token, TokenTree::Punct(_)));
+        if !no_space {
+            result.push(' ');
         }
+        result.push_str(&text);
+
+        // 次のトークンとの間の空白を決める
+        after_repetition = match token {
+            TokenTree::Group(_) => prev_dollar,
+            TokenTree::Punct(p) => after_repetition && !matches!(p.as_char(), '*' | '+' | '?'),
+            _ => false,
+        };
+        glue = match token {
+            TokenTree::Punct(p) => {
+                p.as_char() == '$'
+                    || p.spacing() == Spacing::Joint
+                    // `$name:ident` のフラグメント指定
+                    || (p.as_char() == ':' && i >= 2 && matches!(&tokens[i - 2], TokenTree::Punct(d) if d.as_char() == '$'))
+            }
+            // `$name` の直後の `:` は詰める
+            TokenTree::Ident(_) => {
+                prev_dollar
+                    && matches!(tokens.get(i + 1), Some(TokenTree::Punct(p)) if p.as_char() == ':')
+            }
+            _ => false,
+        };
     }
+    result
 }
 
 // Helper function for formatting trait bounds
@@ -1035,23 +1391,38 @@ fn format_trait_bounds(
 ) -> String {
     bounds
         .iter()
-        .map(|bound| match bound {
-            syn::TypeParamBound::Trait(trait_bound) => trait_bound
-                .path
-                .segments
-                .iter()
-                .map(|segment| segment.ident.to_string())
-                .collect::<Vec<_>>()
-                .join("::"),
-            syn::TypeParamBound::Lifetime(lifetime) => {
-                format!("'{}", lifetime.ident)
-            }
-            _ => "Unknown".to_string(),
-        })
+        .map(format_type_param_bound)
         .collect::<Vec<_>>()
         .join(" + ")
 }
 
+/// `?Sized` や `for<'a> Fn(&'a T)` を含むトレイト境界・ライフタイム境界
+fn format_type_param_bound(bound: &syn::TypeParamBound) -> String {
+    match bound {
+        syn::TypeParamBound::Trait(trait_bound) => {
+            let mut bound_str = format_bound_lifetimes(&trait_bound.lifetimes);
+            if matches!(trait_bound.modifier, syn::TraitBoundModifier::Maybe(_)) {
+                bound_str.push('?');
+            }
+            bound_str.push_str(&extract_path_with_generics(&trait_bound.path));
+            bound_str
+        }
+        syn::TypeParamBound::Lifetime(lifetime) => format!("'{}", lifetime.ident),
+        other => quote::quote!(#other).to_string(),
+    }
+}
+
+/// 高階トレイト境界の `for<'a> `。なければ空文字列
+fn format_bound_lifetimes(lifetimes: &Option<syn::BoundLifetimes>) -> String {
+    match lifetimes {
+        Some(bound) => {
+            let params: Vec<String> = bound.lifetimes.iter().map(format_generic_param).collect();
+            format!("for<{}> ", params.join(", "))
+        }
+        None => String::new(),
+    }
+}
+
 // Helper function for formatting use trees
 fn format_use_tree(tree: &syn::UseTree) -> String {
     match tree {
@@ -1070,6 +1441,54 @@ fn format_use_tree(tree: &syn::UseTree) -> String {
     }
 }
 
+/// `use` がモジュールに束縛する名前（glob は `*`）を順に集める
+fn use_bound_names(tree: &syn::UseTree, parent: Option<&syn::Ident>, names: &mut Vec<String>) {
+    match tree {
+        syn::UseTree::Path(path) => use_bound_names(&path.tree, Some(&path.ident), names),
+        // `use a::{self}` はモジュール `a` を束縛する
+        syn::UseTree::Name(name) if name.ident == "self" => {
+            names.push(parent.unwrap_or(&name.ident).to_string());
+        }
+        syn::UseTree::Name(name) => names.push(name.ident.to_string()),
+        syn::UseTree::Rename(rename) => names.push(rename.rename.to_string()),
+        syn::UseTree::Glob(_) => names.push("*".to_string()),
+        syn::UseTree::Group(group) => {
+            for item in &group.items {
+                use_bound_names(item, parent, names);
+            }
+        }
+    }
+}
+
+/// 現在のモジュールでのパスを作る（例: `a::Debug`、複数なら `a::{Debug, Display}`）
+fn module_binding_path(current_mod: &[String], names: &[String]) -> String {
+    let prefix = if current_mod.is_empty() {
+        String::new()
+    } else {
+        format!("{}::", current_mod.join("::"))
+    };
+    match names {
+        [name] => format!("{}{}", prefix, name),
+        _ => format!("{}{{{}}}", prefix, names.join(", ")),
+    }
+}
+
+/// `use` の見出しと目次に使う、束縛された名前のモジュール内でのパス
+fn use_binding_path(current_mod: &[String], tree: &syn::UseTree) -> String {
+    let mut names = Vec::new();
+    use_bound_names(tree, None, &mut names);
+    module_binding_path(current_mod, &names)
+}
+
+/// `extern crate` の見出しと目次に使うパス。`as` の別名があればそれを使う
+fn extern_crate_binding_path(current_mod: &[String], node: &ItemExternCrate) -> String {
+    let name = node
+        .rename
+        .as_ref()
+        .map_or(&node.ident, |(_, rename)| rename);
+    module_binding_path(current_mod, &[name.to_string()])
+}
+
 // Helper function for simple generic parameter formatting
 fn format_generic_params_simple(
     params: &syn::punctuated::Punctuated<syn::GenericParam, syn::token::Comma>,
@@ -1087,196 +1506,526 @@ fn format_generic_params_simple(
         .join(", ")
 }
 
-fn extract_type_name(ty: &syn::Type) -> String {
-    match ty {
-        syn::Type::Path(type_path) => type_path
-            .path
-            .segments
-            .iter()
-            .map(|s| {
-                let mut segment = s.ident.to_string();
-                if !s.arguments.is_empty() {
-                    match &s.arguments {
-                        syn::PathArguments::AngleBracketed(args) => {
-                            segment.push('<');
-                            let arg_strs: Vec<String> = args
-                                .args
-                                .iter()
-                                .map(|arg| match arg {
-                                    syn::GenericArgument::Type(ty) => extract_type_name(ty),
-                                    syn::GenericArgument::Lifetime(lt) => format!("'{}", lt.ident),
-                                    syn::GenericArgument::Const(_) => "Const".to_string(),
-                                    _ => "Unknown".to_string(),
-                                })
-                                .collect();
-                            segment.push_str(&arg_strs.join(", "));
-                            segment.push('>');
-                        }
-                        syn::PathArguments::Parenthesized(_) => {
-                            segment.push_str("(..)");
-                        }
-                        syn::PathArguments::None => {}
-                    }
-                }
-                segment
-            })
-            .collect::<Vec<_>>()
-            .join("::"),
-        syn::Type::Reference(type_ref) => {
-            let mut ref_str = "&".to_string();
-            if let Some(lifetime) = &type_ref.lifetime {
-                ref_str.push('\'');
-                ref_str.push_str(&lifetime.ident.to_string());
-                ref_str.push(' ');
-            }
-            if type_ref.mutability.is_some() {
-                ref_str.push_str("mut ");
+/// ジェネリクスの宣言を境界とデフォルト値を含めて整形する（例: `<'a, 'b: 'a, T: Clone = (), const N: usize = 4>`）。
+/// パラメータがなければ空文字列
+fn format_generics(generics: &syn::Generics) -> String {
+    if generics.params.is_empty() {
+        return String::new();
+    }
+    let params: Vec<String> = generics.params.iter().map(format_generic_param).collect();
+    format!("<{}>", params.join(", "))
+}
+
+/// impl ブロックのジェネリクスの宣言。impl ではデフォルト値を書けないので省く
+fn format_impl_generics(generics: &syn::Generics) -> String {
+    let mut generics = generics.clone();
+    for param in generics.params.iter_mut() {
+        match param {
+            syn::GenericParam::Type(tp) => {
+                tp.eq_token = None;
+                tp.default = None;
             }
-            ref_str.push_str(&extract_type_name(&type_ref.elem));
-            ref_str
-        }
-        syn::Type::Slice(type_slice) => {
-            format!("[{}]", extract_type_name(&type_slice.elem))
-        }
-        syn::Type::Array(type_array) => {
-            format!("[{}; N]", extract_type_name(&type_array.elem))
-        }
-        syn::Type::Ptr(type_ptr) => {
-            if type_ptr.mutability.is_some() {
-                format!("*mut {}", extract_type_name(&type_ptr.elem))
-            } else {
-                format!("*const {}", extract_type_name(&type_ptr.elem))
+            syn::GenericParam::Const(cp) => {
+                cp.eq_token = None;
+                cp.default = None;
             }
+            syn::GenericParam::Lifetime(_) => {}
         }
-        syn::Type::Tuple(type_tuple) => {
-            let elem_strs: Vec<String> = type_tuple.elems.iter().map(extract_type_name).collect();
-            format!("({})", elem_strs.join(", "))
-        }
-        syn::Type::ImplTrait(_) => "impl Trait".to_string(),
-        syn::Type::TraitObject(_) => "dyn Trait".to_string(),
-        _ => "Unknown".to_string(),
     }
+    format_generics(&generics)
 }
 
-fn extract_pattern_name(pat: &syn::Pat) -> String {
-    match pat {
-        syn::Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
-        syn::Pat::Reference(pat_ref) => extract_pattern_name(&pat_ref.pat),
+fn format_generic_param(param: &syn::GenericParam) -> String {
+    match param {
+        syn::GenericParam::Type(tp) => {
+            let mut type_str = tp.ident.to_string();
+            if !tp.bounds.is_empty() {
+                type_str.push_str(&format!(": {}", format_trait_bounds(&tp.bounds)));
+            }
+            if let Some(default) = &tp.default {
+                type_str.push_str(&format!(" = {}", extract_type_name(default)));
+            }
+            type_str
+        }
+        syn::GenericParam::Lifetime(lp) => {
+            let mut lifetime_str = format!("'{}", lp.lifetime.ident);
+            if !lp.bounds.is_empty() {
+                let bounds: Vec<String> = lp
+                    .bounds
+                    .iter()
+                    .map(|bound| format!("'{}", bound.ident))
+                    .collect();
+                lifetime_str.push_str(&format!(": {}", bounds.join(" + ")));
+            }
+            lifetime_str
+        }
+        syn::GenericParam::Const(cp) => {
+            let mut const_str = format!("const {}: {}", cp.ident, extract_type_name(&cp.ty));
+            if let Some(default) = &cp.default {
+                const_str.push_str(&format!(" = {}", format_expr(default)));
+            }
+            const_str
+        }
+    }
+}
+
+/// where 句を `indent` の字下げで整形する（先頭の改行を含む）。述語がなければ空文字列
+fn format_where_clause(generics: &syn::Generics, indent: &str) -> String {
+    let Some(where_clause) = &generics.where_clause else {
+        return String::new();
+    };
+    if where_clause.predicates.is_empty() {
+        return String::new();
+    }
+    let predicates: Vec<String> = where_clause
+        .predicates
+        .iter()
+        .map(format_where_predicate)
+        .collect();
+    format!(
+        "\n{indent}where\n{indent}    {}",
+        predicates.join(&format!(",\n{indent}    "))
+    )
+}
+
+fn format_where_predicate(predicate: &syn::WherePredicate) -> String {
+    match predicate {
+        syn::WherePredicate::Type(type_pred) => format!(
+            "{}{}: {}",
+            format_bound_lifetimes(&type_pred.lifetimes),
+            extract_type_name(&type_pred.bounded_ty),
+            format_trait_bounds(&type_pred.bounds)
+        ),
+        syn::WherePredicate::Lifetime(lifetime_pred) => {
+            let bounds: Vec<String> = lifetime_pred
+                .bounds
+                .iter()
+                .map(|bound| format!("'{}", bound.ident))
+                .collect();
+            format!("'{}: {}", lifetime_pred.lifetime.ident, bounds.join(" + "))
+        }
+        other => quote::quote!(#other).to_string(),
+    }
+}
+
+/// where 句がある場合は rustfmt と同じく `{` を次の行に置く
+fn open_brace(where_clause: &str) -> &'static str {
+    if where_clause.is_empty() {
+        " {\n"
+    } else {
+        "\n{\n"
+    }
+}
+
+/// 型をソースと同じ形で整形する（例: `[u8; 32]`、`Box<dyn Fn(u8) -> bool + Send>`、`<T as Iterator>::Item`）
+fn extract_type_name(ty: &syn::Type) -> String {
+    match ty {
+        syn::Type::Path(type_path) => {
+            format_qualified_path(type_path.qself.as_ref(), &type_path.path)
+        }
+        syn::Type::Reference(type_ref) => {
+            let mut ref_str = "&".to_string();
+            if let Some(lifetime) = &type_ref.lifetime {
+                ref_str.push('\'');
+                ref_str.push_str(&lifetime.ident.to_string());
+                ref_str.push(' ');
+            }
+            if type_ref.mutability.is_some() {
+                ref_str.push_str("mut ");
+            }
+            ref_str.push_str(&extract_type_name(&type_ref.elem));
+            ref_str
+        }
+        syn::Type::Slice(type_slice) => {
+            format!("[{}]", extract_type_name(&type_slice.elem))
+        }
+        syn::Type::Array(type_array) => {
+            format!(
+                "[{}; {}]",
+                extract_type_name(&type_array.elem),
+                format_expr(&type_array.len)
+            )
+        }
+        syn::Type::Ptr(type_ptr) => {
+            if type_ptr.mutability.is_some() {
+                format!("*mut {}", extract_type_name(&type_ptr.elem))
+            } else {
+                format!("*const {}", extract_type_name(&type_ptr.elem))
+            }
+        }
+        syn::Type::Tuple(type_tuple) => {
+            let elem_strs: Vec<String> = type_tuple.elems.iter().map(extract_type_name).collect();
+            // 要素が 1 つのタプルには末尾のカンマが必要
+            if elem_strs.len() == 1 {
+                format!("({},)", elem_strs[0])
+            } else {
+                format!("({})", elem_strs.join(", "))
+            }
+        }
+        syn::Type::BareFn(bare_fn) => format_bare_fn(bare_fn),
+        syn::Type::ImplTrait(impl_trait) => {
+            format!("impl {}", format_trait_bounds(&impl_trait.bounds))
+        }
+        syn::Type::TraitObject(trait_object) => {
+            format!("dyn {}", format_trait_bounds(&trait_object.bounds))
+        }
+        syn::Type::Paren(paren) => format!("({})", extract_type_name(&paren.elem)),
+        syn::Type::Group(group) => extract_type_name(&group.elem),
+        syn::Type::Never(_) => "!".to_string(),
+        syn::Type::Infer(_) => "_".to_string(),
+        syn::Type::Macro(type_macro) => format_macro(&type_macro.mac),
+        other => tidy_tokens(&quote::quote!(#other).to_string()),
+    }
+}
+
+/// `<T as Trait>::Assoc` のような修飾付きパスも含めてパスを整形する
+fn format_qualified_path(qself: Option<&syn::QSelf>, path: &syn::Path) -> String {
+    let segments: Vec<String> = path.segments.iter().map(format_path_segment).collect();
+    let Some(qself) = qself else {
+        let leading = if path.leading_colon.is_some() {
+            "::"
+        } else {
+            ""
+        };
+        return format!("{}{}", leading, segments.join("::"));
+    };
+
+    let self_ty = extract_type_name(&qself.ty);
+    let (trait_segments, rest) = segments.split_at(qself.position.min(segments.len()));
+    let qualified = if trait_segments.is_empty() {
+        format!("<{}>", self_ty)
+    } else {
+        let leading = if path.leading_colon.is_some() {
+            "::"
+        } else {
+            ""
+        };
+        format!("<{} as {}{}>", self_ty, leading, trait_segments.join("::"))
+    };
+    std::iter::once(qualified)
+        .chain(rest.iter().cloned())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+fn format_path_segment(segment: &syn::PathSegment) -> String {
+    let mut seg_str = segment.ident.to_string();
+    match &segment.arguments {
+        syn::PathArguments::None => {}
+        syn::PathArguments::AngleBracketed(args) => {
+            seg_str.push_str(&format_angle_bracketed_args(args));
+        }
+        // `Fn(u8, &str) -> bool`
+        syn::PathArguments::Parenthesized(args) => {
+            let inputs: Vec<String> = args.inputs.iter().map(extract_type_name).collect();
+            seg_str.push_str(&format!("({})", inputs.join(", ")));
+            if let syn::ReturnType::Type(_, ty) = &args.output {
+                seg_str.push_str(&format!(" -> {}", extract_type_name(ty)));
+            }
+        }
+    }
+    seg_str
+}
+
+fn format_angle_bracketed_args(args: &syn::AngleBracketedGenericArguments) -> String {
+    if args.args.is_empty() {
+        return String::new();
+    }
+    let arg_strs: Vec<String> = args.args.iter().map(format_generic_argument).collect();
+    let turbofish = if args.colon2_token.is_some() {
+        "::"
+    } else {
+        ""
+    };
+    format!("{}<{}>", turbofish, arg_strs.join(", "))
+}
+
+fn format_generic_argument(arg: &syn::GenericArgument) -> String {
+    match arg {
+        syn::GenericArgument::Type(ty) => extract_type_name(ty),
+        syn::GenericArgument::Lifetime(lt) => format!("'{}", lt.ident),
+        syn::GenericArgument::Const(expr) => format_expr(expr),
+        // `Item = u32`
+        syn::GenericArgument::AssocType(assoc_type) => {
+            let generics = assoc_type
+                .generics
+                .as_ref()
+                .map(format_angle_bracketed_args)
+                .unwrap_or_default();
+            format!(
+                "{}{} = {}",
+                assoc_type.ident,
+                generics,
+                extract_type_name(&assoc_type.ty)
+            )
+        }
+        // `N = 4`
+        syn::GenericArgument::AssocConst(assoc_const) => {
+            format!(
+                "{} = {}",
+                assoc_const.ident,
+                format_expr(&assoc_const.value)
+            )
+        }
+        // `Item: Clone + Send`
+        syn::GenericArgument::Constraint(constraint) => {
+            let generics = constraint
+                .generics
+                .as_ref()
+                .map(format_angle_bracketed_args)
+                .unwrap_or_default();
+            format!(
+                "{}{}: {}",
+                constraint.ident,
+                generics,
+                format_trait_bounds(&constraint.bounds)
+            )
+        }
+        other => tidy_tokens(&quote::quote!(#other).to_string()),
+    }
+}
+
+/// 関数ポインタ型（例: `for<'a> unsafe extern "C" fn(&'a u8, ...) -> i32`）
+fn format_bare_fn(bare_fn: &syn::TypeBareFn) -> String {
+    let mut result = format_bound_lifetimes(&bare_fn.lifetimes);
+    if bare_fn.unsafety.is_some() {
+        result.push_str("unsafe ");
+    }
+    if let Some(abi) = &bare_fn.abi {
+        result.push_str(&format_abi(abi));
+    }
+    let mut inputs: Vec<String> = bare_fn
+        .inputs
+        .iter()
+        .map(|arg| match &arg.name {
+            Some((name, _)) => format!("{}: {}", name, extract_type_name(&arg.ty)),
+            None => extract_type_name(&arg.ty),
+        })
+        .collect();
+    if let Some(variadic) = &bare_fn.variadic {
+        inputs.push(match &variadic.name {
+            Some((name, _)) => format!("{}: ...", name),
+            None => "...".to_string(),
+        });
+    }
+    result.push_str(&format!("fn({})", inputs.join(", ")));
+    if let syn::ReturnType::Type(_, ty) = &bare_fn.output {
+        result.push_str(&format!(" -> {}", extract_type_name(ty)));
+    }
+    result
+}
+
+/// `extern "C" ` のような ABI の指定（末尾の空白を含む）
+fn format_abi(abi: &syn::Abi) -> String {
+    match &abi.name {
+        Some(name) => format!("extern \"{}\" ", name.value()),
+        None => "extern ".to_string(),
+    }
+}
+
+/// extern ブロックの `{` の前まで（例: `unsafe extern "C" `）
+fn format_foreign_mod_header(node: &ItemForeignMod) -> String {
+    let unsafety = if node.unsafety.is_some() {
+        "unsafe "
+    } else {
+        ""
+    };
+    format!("{}{}", unsafety, format_abi(&node.abi))
+}
+
+/// 型の位置のマクロ呼び出し（例: `vec_type!(u8)`）
+fn format_macro(mac: &syn::Macro) -> String {
+    let tokens = tidy_tokens(&mac.tokens.to_string());
+    let name = extract_path_with_generics(&mac.path);
+    match mac.delimiter {
+        syn::MacroDelimiter::Paren(_) => format!("{}!({})", name, tokens),
+        syn::MacroDelimiter::Bracket(_) => format!("{}![{}]", name, tokens),
+        syn::MacroDelimiter::Brace(_) => format!("{}! {{ {} }}", name, tokens),
+    }
+}
+
+/// 配列の長さや const ジェネリクスの引数などの式
+fn format_expr(expr: &syn::Expr) -> String {
+    match expr {
+        syn::Expr::Lit(lit) => quote::quote!(#lit).to_string(),
+        syn::Expr::Path(expr_path) => {
+            format_qualified_path(expr_path.qself.as_ref(), &expr_path.path)
+        }
+        syn::Expr::Binary(binary) => {
+            let op = &binary.op;
+            format!(
+                "{} {} {}",
+                format_expr(&binary.left),
+                quote::quote!(#op),
+                format_expr(&binary.right)
+            )
+        }
+        syn::Expr::Unary(unary) => {
+            let op = &unary.op;
+            format!("{}{}", quote::quote!(#op), format_expr(&unary.expr))
+        }
+        syn::Expr::Paren(paren) => format!("({})", format_expr(&paren.expr)),
+        syn::Expr::Block(block) => match block.block.stmts.as_slice() {
+            [syn::Stmt::Expr(inner, None)] => format!("{{ {} }}", format_expr(inner)),
+            _ => tidy_tokens(&quote::quote!(#block).to_string()),
+        },
+        other => tidy_tokens(&quote::quote!(#other).to_string()),
+    }
+}
+
+/// `quote` が出力するトークン間の空白のうち、Rust の表記として不自然なものを詰める
+fn tidy_tokens(tokens: &str) -> String {
+    tokens
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" ,", ",")
+        .replace(" ;", ";")
+        .replace("& ", "&")
+        .replace(" (", "(")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace("< ", "<")
+        .replace(" <", "<")
+        .replace(" >", ">")
+        .replace(" !", "!")
+}
+
+/// 引数のパターン。rustdoc と同じく束縛の `mut` は省略する
+fn extract_pattern_name(pat: &syn::Pat) -> String {
+    match pat {
+        syn::Pat::Ident(pat_ident) => {
+            let mut name = String::new();
+            if pat_ident.by_ref.is_some() {
+                name.push_str("ref ");
+            }
+            name.push_str(&pat_ident.ident.to_string());
+            if let Some((_, subpat)) = &pat_ident.subpat {
+                name.push_str(&format!(" @ {}", extract_pattern_name(subpat)));
+            }
+            name
+        }
+        syn::Pat::Reference(pat_ref) => {
+            let mutability = if pat_ref.mutability.is_some() {
+                "mut "
+            } else {
+                ""
+            };
+            format!("&{}{}", mutability, extract_pattern_name(&pat_ref.pat))
+        }
         syn::Pat::Type(pat_type) => extract_pattern_name(&pat_type.pat),
         syn::Pat::Wild(_) => "_".to_string(),
+        syn::Pat::Rest(_) => "..".to_string(),
+        syn::Pat::Paren(pat_paren) => format!("({})", extract_pattern_name(&pat_paren.pat)),
         syn::Pat::Tuple(pat_tuple) => {
             let names: Vec<String> = pat_tuple.elems.iter().map(extract_pattern_name).collect();
             format!("({})", names.join(", "))
         }
+        syn::Pat::TupleStruct(pat_tuple_struct) => {
+            let names: Vec<String> = pat_tuple_struct
+                .elems
+                .iter()
+                .map(extract_pattern_name)
+                .collect();
+            format!(
+                "{}({})",
+                format_qualified_path(pat_tuple_struct.qself.as_ref(), &pat_tuple_struct.path),
+                names.join(", ")
+            )
+        }
         syn::Pat::Struct(pat_struct) => {
-            let struct_name = pat_struct
-                .path
-                .segments
+            let mut fields: Vec<String> = pat_struct
+                .fields
                 .iter()
-                .map(|s| s.ident.to_string())
-                .collect::<Vec<_>>()
-                .join("::");
-            format!("{} {{ .. }}", struct_name)
+                .map(|field| {
+                    let member = match &field.member {
+                        syn::Member::Named(ident) => ident.to_string(),
+                        syn::Member::Unnamed(index) => index.index.to_string(),
+                    };
+                    match field.colon_token {
+                        Some(_) => format!("{}: {}", member, extract_pattern_name(&field.pat)),
+                        // `Point { x, .. }` の省略形
+                        None => extract_pattern_name(&field.pat),
+                    }
+                })
+                .collect();
+            if pat_struct.rest.is_some() {
+                fields.push("..".to_string());
+            }
+            format!(
+                "{} {{ {} }}",
+                format_qualified_path(pat_struct.qself.as_ref(), &pat_struct.path),
+                fields.join(", ")
+            )
         }
-        _ => "param".to_string(),
+        syn::Pat::Slice(pat_slice) => {
+            let names: Vec<String> = pat_slice.elems.iter().map(extract_pattern_name).collect();
+            format!("[{}]", names.join(", "))
+        }
+        other => tidy_tokens(&quote::quote!(#other).to_string()),
     }
 }
 
-fn format_function_signature(
-    sig: &syn::Signature,
-    include_pub: bool,
-    where_indent: &str,
-) -> String {
+/// `&self`、`&'a mut self`、`self: Pin<&mut Self>` などのレシーバー
+fn format_receiver(recv: &syn::Receiver) -> String {
+    let mutability = if recv.mutability.is_some() {
+        "mut "
+    } else {
+        ""
+    };
+    match &recv.reference {
+        // 型を明示したレシーバーと値で受け取る `mut self` の `mut` は束縛の修飾なので省略する
+        _ if recv.colon_token.is_some() => format!("self: {}", extract_type_name(&recv.ty)),
+        Some((_, Some(lifetime))) => format!("&'{} {}self", lifetime.ident, mutability),
+        Some((_, None)) => format!("&{}self", mutability),
+        None => "self".to_string(),
+    }
+}
+
+fn format_function_signature(sig: &syn::Signature, vis: &str, where_indent: &str) -> String {
     let mut result = String::new();
 
-    // pub fn or fn
-    if include_pub {
-        result.push_str("pub fn ");
-    } else {
-        result.push_str("fn ");
+    // 可視性と修飾子（`const async unsafe extern "C"` の順）
+    result.push_str(vis);
+    if sig.constness.is_some() {
+        result.push_str("const ");
+    }
+    if sig.asyncness.is_some() {
+        result.push_str("async ");
     }
+    if sig.unsafety.is_some() {
+        result.push_str("unsafe ");
+    }
+    if let Some(abi) = &sig.abi {
+        result.push_str(&format_abi(abi));
+    }
+    result.push_str("fn ");
 
     // 関数名
     result.push_str(&sig.ident.to_string());
 
     // ジェネリクス
-    if !sig.generics.params.is_empty() {
-        result.push('<');
-        let generics: Vec<String> = sig
-            .generics
-            .params
-            .iter()
-            .map(|p| match p {
-                syn::GenericParam::Type(tp) => {
-                    let mut type_str = tp.ident.to_string();
-                    if !tp.bounds.is_empty() {
-                        type_str.push_str(": ");
-                        let bounds: Vec<String> = tp
-                            .bounds
-                            .iter()
-                            .map(|bound| match bound {
-                                syn::TypeParamBound::Trait(trait_bound) => trait_bound
-                                    .path
-                                    .segments
-                                    .iter()
-                                    .map(|s| s.ident.to_string())
-                                    .collect::<Vec<_>>()
-                                    .join("::"),
-                                syn::TypeParamBound::Lifetime(lifetime) => {
-                                    format!("'{}", lifetime.ident)
-                                }
-                                _ => "Bound".to_string(),
-                            })
-                            .collect();
-                        type_str.push_str(&bounds.join(" + "));
-                    }
-                    type_str
-                }
-                syn::GenericParam::Lifetime(lp) => {
-                    let mut lifetime_str = format!("'{}", lp.lifetime.ident);
-                    if !lp.bounds.is_empty() {
-                        lifetime_str.push_str(": ");
-                        let bounds: Vec<String> = lp
-                            .bounds
-                            .iter()
-                            .map(|bound| format!("'{}", bound.ident))
-                            .collect();
-                        lifetime_str.push_str(&bounds.join(" + "));
-                    }
-                    lifetime_str
-                }
-                syn::GenericParam::Const(cp) => {
-                    format!("const {}: {}", cp.ident, extract_type_name(&cp.ty))
-                }
-            })
-            .collect();
-        result.push_str(&generics.join(", "));
-        result.push('>');
-    }
+    result.push_str(&format_generics(&sig.generics));
 
     // パラメータ
     result.push('(');
-    let params: Vec<String> = sig
+    let mut params: Vec<String> = sig
         .inputs
         .iter()
         .map(|input| match input {
-            syn::FnArg::Receiver(recv) => {
-                let mut param = String::new();
-                if recv.reference.is_some() {
-                    param.push('&');
-                    if recv.mutability.is_some() {
-                        param.push_str("mut ");
-                    }
-                }
-                param.push_str("self");
-                param
-            }
+            syn::FnArg::Receiver(recv) => format_receiver(recv),
             syn::FnArg::Typed(pat_type) => {
                 let param_name = extract_pattern_name(&pat_type.pat);
                 format!("{}: {}", param_name, extract_type_name(&pat_type.ty))
             }
         })
         .collect();
+    // C の可変長引数
+    if let Some(variadic) = &sig.variadic {
+        params.push(match &variadic.pat {
+            Some((pat, _)) => format!("{}: ...", extract_pattern_name(pat)),
+            None => "...".to_string(),
+        });
+    }
 
     // パラメータを1行にまとめて表示
     result.push_str(&params.join(", "));
@@ -1289,120 +2038,13 @@ fn format_function_signature(
     }
 
     // where句
-    if let Some(where_clause) = &sig.generics.where_clause {
-        if !where_clause.predicates.is_empty() {
-            result.push_str(&format!("\n{}where\n{}    ", where_indent, where_indent));
-            result.push_str(&extract_where_clause(where_clause));
-        }
-    }
+    result.push_str(&format_where_clause(&sig.generics, where_indent));
 
     result
 }
 
-fn extract_where_clause(where_clause: &syn::WhereClause) -> String {
-    let predicates: Vec<String> = where_clause
-        .predicates
-        .iter()
-        .map(|predicate| match predicate {
-            syn::WherePredicate::Type(type_pred) => {
-                let bounded_ty = extract_type_name(&type_pred.bounded_ty);
-                let bounds: Vec<String> = type_pred
-                    .bounds
-                    .iter()
-                    .map(|bound| match bound {
-                        syn::TypeParamBound::Trait(trait_bound) => trait_bound
-                            .path
-                            .segments
-                            .iter()
-                            .map(|s| s.ident.to_string())
-                            .collect::<Vec<_>>()
-                            .join("::"),
-                        syn::TypeParamBound::Lifetime(lifetime) => {
-                            format!("'{}", lifetime.ident)
-                        }
-                        _ => "Bound".to_string(),
-                    })
-                    .collect();
-                format!("{}: {}", bounded_ty, bounds.join(" + "))
-            }
-            syn::WherePredicate::Lifetime(lifetime_pred) => {
-                let lifetime = &lifetime_pred.lifetime;
-                let bounds: Vec<String> = lifetime_pred
-                    .bounds
-                    .iter()
-                    .map(|bound| format!("'{}", bound.ident))
-                    .collect();
-                if bounds.is_empty() {
-                    format!("'{}", lifetime.ident)
-                } else {
-                    format!("'{}: {}", lifetime.ident, bounds.join(" + "))
-                }
-            }
-            _ => "Where".to_string(),
-        })
-        .collect();
-
-    predicates.join(",\n    ")
-}
-
 fn extract_path_with_generics(path: &syn::Path) -> String {
-    path.segments
-        .iter()
-        .map(|segment| {
-            let mut seg_str = segment.ident.to_string();
-
-            // ジェネリクス引数があるかチェック
-            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                if !args.args.is_empty() {
-                    seg_str.push('<');
-                    let generic_args: Vec<String> = args
-                        .args
-                        .iter()
-                        .map(|arg| {
-                            match arg {
-                                syn::GenericArgument::Type(ty) => extract_type_name(ty),
-                                syn::GenericArgument::Lifetime(lt) => format!("'{}", lt.ident),
-                                syn::GenericArgument::Const(expr) => {
-                                    // const式を文字列化するのは複雑なので、簡単な場合のみ対応
-                                    match expr {
-                                        syn::Expr::Lit(lit) => match &lit.lit {
-                                            syn::Lit::Str(s) => format!("\"{}\"", s.value()),
-                                            syn::Lit::Int(i) => i.base10_digits().to_string(),
-                                            syn::Lit::Bool(b) => b.value.to_string(),
-                                            _ => "Const".to_string(),
-                                        },
-                                        syn::Expr::Path(path) => path
-                                            .path
-                                            .segments
-                                            .iter()
-                                            .map(|s| s.ident.to_string())
-                                            .collect::<Vec<_>>()
-                                            .join("::"),
-                                        _ => "Const".to_string(),
-                                    }
-                                }
-                                syn::GenericArgument::AssocType(assoc_type) => {
-                                    format!(
-                                        "{} = {}",
-                                        assoc_type.ident,
-                                        extract_type_name(&assoc_type.ty)
-                                    )
-                                }
-                                syn::GenericArgument::AssocConst(_) => "AssocConst".to_string(),
-                                syn::GenericArgument::Constraint(_) => "Constraint".to_string(),
-                                _ => "GenericArg".to_string(),
-                            }
-                        })
-                        .collect();
-                    seg_str.push_str(&generic_args.join(", "));
-                    seg_str.push('>');
-                }
-            }
-
-            seg_str
-        })
-        .collect::<Vec<_>>()
-        .join("::")
+    format_qualified_path(None, path)
 }
 
 fn extract_cfg_attributes(attrs: &[syn::Attribute]) -> Vec<String> {
@@ -1461,16 +2103,11 @@ fn extract_derives(attrs: &[syn::Attribute]) -> Vec<String> {
 
     for attr in attrs {
         if attr.path().is_ident("derive") {
-            if let syn::Meta::List(meta_list) = &attr.meta {
-                // derive(Clone, Debug) のような形式をパース
-                let derive_string = meta_list.tokens.to_string();
-                // 簡単なパースで derive 名を抽出
-                for part in derive_string.split(',') {
-                    let derive_name = part.trim().to_string();
-                    if !derive_name.is_empty() {
-                        derives.push(derive_name);
-                    }
-                }
+            // derive(Clone, serde::Serialize) のような形式をパース
+            if let Ok(paths) =
+                attr.parse_args_with(Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)
+            {
+                derives.extend(paths.iter().map(extract_path_with_generics));
             }
         }
     }
@@ -1478,53 +2115,807 @@ fn extract_derives(attrs: &[syn::Attribute]) -> Vec<String> {
     derives
 }
 
-impl<'a> CompleteDocsVisitor<'a> {
-    pub fn extract_docs_for_item(&mut self, attrs: &[syn::Attribute]) {
-        for attr in attrs {
-            if let Ok(meta) = attr.meta.require_name_value() {
-                if meta.path.is_ident("doc") {
-                    if let syn::Expr::Lit(syn::ExprLit {
-                        lit: syn::Lit::Str(lit_str),
-                        ..
-                    }) = &meta.value
-                    {
-                        let doc_content = lit_str.value();
-                        let doc_content = doc_content.trim();
-
-                        // docsコメント内の見出しレベルを調整
-                        let adjusted_content =
-                            if let Some(stripped) = doc_content.strip_prefix("# ") {
-                                format!("#### {}", stripped)
-                            } else if let Some(stripped) = doc_content.strip_prefix("## ") {
-                                format!("##### {}", stripped)
-                            } else if let Some(stripped) = doc_content.strip_prefix("### ") {
-                                format!("###### {}", stripped)
-                            } else {
-                                doc_content.to_string()
-                            };
-
-                        self.content.push_str(&adjusted_content);
-                        self.content.push('\n');
-                    }
-                }
+/// `#[repr(...)]` 属性を書かれたとおりに整形する（例: `#[repr(C, packed(2))]`）
+fn format_repr_attributes(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|attr| {
+            let list = attr.meta.require_list().ok()?;
+            list.path
+                .is_ident("repr")
+                .then(|| format!("#[repr({})]", tidy_tokens(&list.tokens.to_string())))
+        })
+        .collect()
+}
+
+/// フィールドやバリアントの docs をコードブロック内の `///` コメントとして整形する
+fn format_doc_comment(attrs: &[Attribute], source_dir: Option<&Path>, indent: &str) -> String {
+    let lines = doc_text_lines(attrs, source_dir, true);
+    let Some(start) = lines.iter().position(|line| !line.is_empty()) else {
+        return String::new();
+    };
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .unwrap_or(start);
+    lines[start..=end]
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                format!("{}///\n", indent)
+            } else {
+                format!("{}/// {}\n", indent, line)
             }
-        }
-        self.content.push('\n');
+        })
+        .collect()
+}
+
+/// `format_doc_comment` で出力する docs があるかどうか
+fn has_doc_lines(attrs: &[Attribute], source_dir: Option<&Path>) -> bool {
+    doc_text_lines(attrs, source_dir, false)
+        .iter()
+        .any(|line| !line.is_empty())
+}
+
+/// タプル構造体・タプルバリアントのフィールドを `(...)` として整形する。
+/// docs のあるフィールドがあれば 1 行に 1 フィールドで出力する
+fn format_tuple_fields(fields: &[&syn::Field], source_dir: Option<&Path>, indent: &str) -> String {
+    if !fields.iter().any(|f| has_doc_lines(&f.attrs, source_dir)) {
+        let field_types: Vec<String> = fields
+            .iter()
+            .map(|f| format!("{}{}", format_visibility(&f.vis), extract_type_name(&f.ty)))
+            .collect();
+        return format!("({})", field_types.join(", "));
+    }
+
+    let field_indent = format!("{}    ", indent);
+    let mut result = "(\n".to_string();
+    for f in fields {
+        result.push_str(&format_doc_comment(&f.attrs, source_dir, &field_indent));
+        result.push_str(&format!(
+            "{}{}{},\n",
+            field_indent,
+            format_visibility(&f.vis),
+            extract_type_name(&f.ty)
+        ));
     }
+    result.push_str(indent);
+    result.push(')');
+    result
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use syn::visit::Visit;
+/// impl ブロックのヘッダーを整形する（例: `impl<T> Display for Wrapper<T>`）
+fn format_impl_header(node: &ItemImpl) -> String {
+    let mut header = String::new();
+    if node.unsafety.is_some() {
+        header.push_str("unsafe ");
+    }
+    header.push_str("impl");
+    header.push_str(&format_generics(&node.generics));
+    header.push(' ');
 
-    #[test]
-    fn test_extract_pattern_name() {
-        // Test different pattern types by constructing them manually
-        use syn::{PatIdent, PatWild};
+    if let Some((negative, trait_path, _)) = &node.trait_ {
+        if negative.is_some() {
+            header.push('!');
+        }
+        header.push_str(&format!("{} for ", extract_path_with_generics(trait_path)));
+    }
+    header.push_str(&extract_type_name(&node.self_ty));
+    header.push_str(&format_where_clause(&node.generics, ""));
+    header
+}
 
-        // Test identifier pattern
-        let ident = syn::Ident::new("x", proc_macro2::Span::call_site());
+/// docsコメント内の見出しレベルを調整する
+fn adjust_doc_heading(line: &str) -> String {
+    if let Some(stripped) = line.strip_prefix("# ") {
+        format!("#### {}", stripped)
+    } else if let Some(stripped) = line.strip_prefix("## ") {
+        format!("##### {}", stripped)
+    } else if let Some(stripped) = line.strip_prefix("### ") {
+        format!("###### {}", stripped)
+    } else {
+        line.to_string()
+    }
+}
+
+/// アイテムの属性を取得する
+fn item_attrs(item: &Item) -> &[Attribute] {
+    match item {
+        Item::Const(item) => &item.attrs,
+        Item::Enum(item) => &item.attrs,
+        Item::ExternCrate(item) => &item.attrs,
+        Item::Fn(item) => &item.attrs,
+        Item::ForeignMod(item) => &item.attrs,
+        Item::Impl(item) => &item.attrs,
+        Item::Macro(item) => &item.attrs,
+        Item::Mod(item) => &item.attrs,
+        Item::Static(item) => &item.attrs,
+        Item::Struct(item) => &item.attrs,
+        Item::Trait(item) => &item.attrs,
+        Item::TraitAlias(item) => &item.attrs,
+        Item::Type(item) => &item.attrs,
+        Item::Union(item) => &item.attrs,
+        Item::Use(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+/// `#[doc(..)]` と `#[cfg_attr(.., doc(..))]` の中身を列挙する
+fn doc_attribute_entries(attrs: &[Attribute]) -> Vec<syn::Meta> {
+    fn collect(meta: &syn::Meta, entries: &mut Vec<syn::Meta>) {
+        let syn::Meta::List(list) = meta else {
+            return;
+        };
+        let Ok(nested) =
+            list.parse_args_with(Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)
+        else {
+            return;
+        };
+        if list.path.is_ident("doc") {
+            entries.extend(nested);
+        } else if list.path.is_ident("cfg_attr") {
+            // 最初の要素は条件なので読み飛ばす
+            for meta in nested.iter().skip(1) {
+                collect(meta, entries);
+            }
+        }
+    }
+
+    let mut entries = Vec::new();
+    for attr in attrs {
+        collect(&attr.meta, &mut entries);
+    }
+    entries
+}
+
+/// `#[doc(hidden)]` が付いているかどうか
+pub fn is_doc_hidden(attrs: &[Attribute]) -> bool {
+    doc_attribute_entries(attrs)
+        .iter()
+        .any(|meta| matches!(meta, syn::Meta::Path(path) if path.is_ident("hidden")))
+}
+
+/// docsコメント（`#[doc = "..."]`）の行を列挙する
+pub fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|attr| {
+            let meta = attr.meta.require_name_value().ok()?;
+            if !meta.path.is_ident("doc") {
+                return None;
+            }
+            match &meta.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) => Some(lit_str.value()),
+                _ => None,
+            }
+        })
+        .flat_map(|doc| {
+            doc.split('\n')
+                .map(|l| l.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// docsコメントの最初の段落を1行にまとめて取得する。`include_str!` の docs は `source_dir` から読み込み、
+/// README を読み込んだ場合などの先頭の見出しは飛ばす
+pub fn doc_summary(attrs: &[Attribute], source_dir: Option<&Path>) -> Option<String> {
+    let summary = doc_text_lines(attrs, source_dir, false)
+        .iter()
+        .map(|line| line.trim())
+        .skip_while(|line| line.is_empty() || line.starts_with('#'))
+        .take_while(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!summary.is_empty()).then_some(summary)
+}
+
+/// `#[doc = ".."]` と `#[doc = include_str!("..")]` の内容を行ごとに返す。
+/// `include_str!` はソースファイルのディレクトリ `source_dir` からの相対パスで読み込み、
+/// 読み込めなければ `warn` の場合に警告を出す
+pub fn doc_text_lines(attrs: &[Attribute], source_dir: Option<&Path>, warn: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        if let Ok(meta) = attr.meta.require_name_value() {
+            if meta.path.is_ident("doc") {
+                match &meta.value {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit_str),
+                        ..
+                    }) => {
+                        // `///` が付ける先頭の空白 1 つだけを取り除き、コード例の字下げは残す
+                        lines.extend(
+                            lit_str
+                                .value()
+                                .split('\n')
+                                .map(|l| l.strip_prefix(' ').unwrap_or(l).trim_end().to_string()),
+                        );
+                    }
+                    syn::Expr::Macro(expr_macro) => {
+                        if let Some(included) = read_included_doc(&expr_macro.mac, source_dir, warn)
+                        {
+                            lines.extend(included.lines().map(|l| l.trim_end().to_string()));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    lines
+}
+
+fn read_included_doc(mac: &syn::Macro, source_dir: Option<&Path>, warn: bool) -> Option<String> {
+    if !mac.path.is_ident("include_str") {
+        return None;
+    }
+    let relative: syn::LitStr = mac.parse_body().ok()?;
+    let path = match source_dir {
+        Some(dir) => dir.join(relative.value()),
+        None => PathBuf::from(relative.value()),
+    };
+    match fs::read_to_string(&path) {
+        Ok(content) => Some(content),
+        Err(err) => {
+            if warn {
+                eprintln!(
+                    "warning: could not read included doc {}: {}",
+                    path.display(),
+                    err
+                );
+            }
+            None
+        }
+    }
+}
+
+/// docsコメント（`include_str!` を含む）があるかどうか
+fn has_docs(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.meta
+            .require_name_value()
+            .is_ok_and(|meta| meta.path.is_ident("doc"))
+    })
+}
+
+/// `#[doc(cfg(..))]` の条件を rustdoc と同様のバッジ文言に整形する
+fn format_doc_cfg_badge(attrs: &[Attribute]) -> Option<String> {
+    let conditions: Vec<String> = doc_attribute_entries(attrs)
+        .iter()
+        .filter_map(|meta| match meta {
+            syn::Meta::List(list) if list.path.is_ident("cfg") => {
+                list.parse_args::<syn::Meta>().ok()
+            }
+            _ => None,
+        })
+        .map(|predicate| match &predicate {
+            syn::Meta::NameValue(nv) if nv.path.is_ident("feature") => {
+                format!("crate feature `{}`", format_cfg_value(&nv.value))
+            }
+            _ => format!("`{}`", format_cfg_predicate(&predicate)),
+        })
+        .collect();
+
+    (!conditions.is_empty()).then(|| format!("Available on {} only.", conditions.join(" and ")))
+}
+
+/// cfg の条件式を整形する（例: `all(unix, feature = "serde")`）
+fn format_cfg_predicate(meta: &syn::Meta) -> String {
+    let path_str = |path: &syn::Path| {
+        path.segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::")
+    };
+
+    match meta {
+        syn::Meta::Path(path) => path_str(path),
+        syn::Meta::NameValue(nv) => {
+            format!(
+                "{} = \"{}\"",
+                path_str(&nv.path),
+                format_cfg_value(&nv.value)
+            )
+        }
+        syn::Meta::List(list) => {
+            match list.parse_args_with(Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated) {
+                Ok(nested) => format!(
+                    "{}({})",
+                    path_str(&list.path),
+                    nested
+                        .iter()
+                        .map(format_cfg_predicate)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Err(_) => format!("{}({})", path_str(&list.path), list.tokens),
+            }
+        }
+    }
+}
+
+fn format_cfg_value(expr: &syn::Expr) -> String {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit_str),
+            ..
+        }) => lit_str.value(),
+        _ => quote::quote!(#expr).to_string(),
+    }
+}
+
+impl<'a> CompleteDocsVisitor<'a> {
+    pub fn new(content: &'a mut String) -> Self {
+        Self {
+            content,
+            current_mod: Vec::new(),
+            public_api: None,
+            visibility: VisibilityLevel::default(),
+            include_hidden: false,
+            impls: None,
+            source_dir: None,
+            source_file: PathBuf::new(),
+            submodules: Vec::new(),
+            items: Vec::new(),
+            module_docs: None,
+            target: None,
+        }
+    }
+
+    /// モジュールの見出し・概要・アイテムを出力する。インラインモジュールは
+    /// `submodules` に別セクションとして出力される。出力するものがなければ見出しも省略する
+    pub fn render_module(&mut self, attrs: &[Attribute], items: &[Item], documented: bool) {
+        let start = self.content.len();
+
+        // `///` と `//!` をモジュールの概要として出力する
+        if documented {
+            self.push_doc_cfg_badge(attrs);
+            if has_docs(attrs) {
+                self.extract_docs_for_item(attrs);
+                self.module_docs = self.item_docs(attrs);
+            }
+        }
+
+        for item in items {
+            self.visit_item(item);
+        }
+
+        if self.content.len() > start {
+            self.content.insert_str(
+                start,
+                &format_module_heading(
+                    &self.current_mod,
+                    self.target.as_deref(),
+                    &self.source_file,
+                ),
+            );
+            self.content.push('\n');
+        }
+    }
+
+    /// シグネチャのコードブロックを開始し、シグネチャの開始位置を返す
+    fn begin_signature(&mut self) -> usize {
+        self.content.push_str("```rust\n");
+        self.content.len()
+    }
+
+    /// シグネチャのコードブロックを閉じて docs を出力し、アイテムをモデルに記録する
+    fn end_entry(
+        &mut self,
+        kind: ItemKind,
+        path: &str,
+        signature_start: usize,
+        attrs: &[Attribute],
+        span: proc_macro2::Span,
+    ) {
+        let signature = self.content[signature_start..].trim_end().to_string();
+        if !self.content.ends_with('\n') {
+            self.content.push('\n');
+        }
+        self.content.push_str("```\n\n");
+        self.extract_docs_for_item(attrs);
+        self.record_item(kind, path, signature, attrs, span);
+    }
+
+    fn record_item(
+        &mut self,
+        kind: ItemKind,
+        path: &str,
+        signature: String,
+        attrs: &[Attribute],
+        span: proc_macro2::Span,
+    ) {
+        let source_file = self.source_file.to_string_lossy().replace('\\', "/");
+        self.items.push(ItemModel {
+            kind,
+            path: path.to_string(),
+            signature,
+            docs: self.item_docs(attrs),
+            cfg: extract_cfg_attributes(attrs),
+            derives: extract_derives(attrs),
+            span: (!source_file.is_empty()).then(|| Span::new(&source_file, span)),
+        });
+    }
+
+    /// 可視性レベルと `#[doc(hidden)]` を考慮して出力対象かどうかを判定する
+    fn is_documented_member(&self, vis: &Visibility, attrs: &[Attribute]) -> bool {
+        self.visibility.includes(vis) && (self.include_hidden || !is_doc_hidden(attrs))
+    }
+
+    fn item_path(&self, vis: &Visibility, ident: &syn::Ident) -> Option<ItemPath> {
+        resolve_item_path(
+            self.public_api,
+            self.visibility,
+            &self.current_mod,
+            vis,
+            &ident.to_string(),
+        )
+    }
+
+    /// 手続き的マクロを関数としてではなく呼び出し方で出力する
+    fn push_proc_macro(&mut self, node: &ItemFn, proc_macro: &ProcMacro) {
+        if self.item_path(&node.vis, &node.sig.ident).is_none() {
+            return;
+        }
+        let ident = &node.sig.ident;
+        self.content
+            .push_str(&format!("### {}\n\n", proc_macro.heading(ident)));
+        self.push_doc_cfg_badge(&node.attrs);
+        let signature_start = self.begin_signature();
+        self.content.push_str(&proc_macro.usage(ident));
+        self.end_entry(
+            proc_macro.kind(),
+            &proc_macro.path(ident),
+            signature_start,
+            &node.attrs,
+            node.span(),
+        );
+    }
+
+    /// アイテムの見出しと、再エクスポートされている場合はその定義位置を出力する
+    fn push_item_heading(&mut self, item_path: &ItemPath, attrs: &[Attribute]) {
+        self.content
+            .push_str(&format!("### {}\n\n", item_path.path));
+        if let Some(defined_at) = &item_path.defined_at {
+            self.content
+                .push_str(&format!("*Re-exported from `{}`*\n\n", defined_at));
+        }
+        if !item_path.aliases.is_empty() {
+            let aliases: Vec<String> = item_path
+                .aliases
+                .iter()
+                .map(|alias| format!("`{}`", alias))
+                .collect();
+            self.content
+                .push_str(&format!("*Also available as {}*\n\n", aliases.join(", ")));
+        }
+        self.push_doc_cfg_badge(attrs);
+    }
+
+    /// 型に対する impl ブロックを "Methods" と "Trait Implementations" に分けて型のエントリの下に出力する
+    fn push_type_impls(
+        &mut self,
+        item_path: &ItemPath,
+        ident: &syn::Ident,
+        generics: &syn::Generics,
+        attrs: &[Attribute],
+    ) {
+        let Some(index) = self.impls else {
+            return;
+        };
+        let mut type_path = self.current_mod.clone();
+        type_path.push(ident.to_string());
+
+        let blocks: Vec<&ImplBlock> = index
+            .impls_for(&type_path)
+            .iter()
+            .filter(|block| self.include_hidden || !is_doc_hidden(&block.item.attrs))
+            .collect();
+        let (trait_blocks, inherent_blocks): (Vec<&ImplBlock>, Vec<&ImplBlock>) = blocks
+            .into_iter()
+            .partition(|block| block.item.trait_.is_some());
+        let derives = extract_derives(attrs);
+
+        // ブランケット実装の境界は実装済みのトレイト名で判定する
+        let mut implemented: HashSet<String> = derives
+            .iter()
+            .filter_map(|derive| derive.rsplit("::").next())
+            .map(str::to_string)
+            .collect();
+        for block in &trait_blocks {
+            if let Some((None, trait_path, _)) = &block.item.trait_ {
+                if let Some(segment) = trait_path.segments.last() {
+                    implemented.insert(segment.ident.to_string());
+                }
+            }
+        }
+        let blankets = index.blanket_impls_for(&implemented);
+
+        let source_dir = self.source_dir.take();
+
+        let methods: Vec<(&ImplBlock, Vec<&syn::ImplItem>)> = inherent_blocks
+            .into_iter()
+            .map(|block| (block, self.documented_impl_items(&block.item)))
+            .filter(|(block, items)| !items.is_empty() || has_docs(&block.item.attrs))
+            .collect();
+        if !methods.is_empty() {
+            self.content.push_str("#### Methods\n\n");
+            for (block, items) in methods {
+                self.source_dir = block.source_dir.clone();
+                self.push_impl_header(&block.item);
+                self.push_impl_items(&items, "#####", &item_path.path, &item_path.path);
+            }
+        }
+
+        if !trait_blocks.is_empty() || !derives.is_empty() || !blankets.is_empty() {
+            self.content.push_str("#### Trait Implementations\n\n");
+            for block in trait_blocks {
+                self.source_dir = block.source_dir.clone();
+                self.render_impl_block(&block.item, "#####", &item_path.path);
+            }
+
+            let self_type = if generics.params.is_empty() {
+                ident.to_string()
+            } else {
+                format!(
+                    "{}<{}>",
+                    ident,
+                    format_generic_params_simple(&generics.params)
+                )
+            };
+            let impl_generics = format_impl_generics(generics);
+            // where 句も 1 行にまとめる
+            let where_clause = format_where_clause(generics, "")
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            let where_clause = if where_clause.is_empty() {
+                where_clause
+            } else {
+                format!(" {}", where_clause)
+            };
+            let mut summary = Vec::new();
+            for derive in &derives {
+                summary.push(format!(
+                    "- `impl{} {} for {}{}` (derived)\n",
+                    impl_generics, derive, self_type, where_clause
+                ));
+            }
+            for block in blankets {
+                // where 句も 1 行にまとめる
+                let header = format_impl_header(&block.item)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                summary.push(format!("- `{}` (blanket implementation)\n", header));
+            }
+            if !summary.is_empty() {
+                self.content.push_str(&summary.concat());
+                self.content.push('\n');
+            }
+        }
+
+        self.source_dir = source_dir;
+    }
+
+    /// 出力対象となる impl ブロックの関連アイテム。
+    /// トレイト実装の関連アイテムはトレイトと同じ可視性を持つ
+    fn documented_impl_items<'n>(&self, node: &'n ItemImpl) -> Vec<&'n syn::ImplItem> {
+        let is_trait_impl = node.trait_.is_some();
+        node.items
+            .iter()
+            .filter(|item| {
+                let (vis, attrs) = match item {
+                    syn::ImplItem::Fn(i) => (&i.vis, &i.attrs),
+                    syn::ImplItem::Const(i) => (&i.vis, &i.attrs),
+                    syn::ImplItem::Type(i) => (&i.vis, &i.attrs),
+                    _ => return false,
+                };
+                if is_trait_impl {
+                    self.include_hidden || !is_doc_hidden(attrs)
+                } else {
+                    self.is_documented_member(vis, attrs)
+                }
+            })
+            .collect()
+    }
+
+    /// impl ブロックの見出しと、関連アイテム（メソッド・定数・関連型）を個別のエントリとして出力する
+    fn render_impl_block(&mut self, node: &ItemImpl, heading: &str, owner: &str) {
+        if !self.include_hidden && is_doc_hidden(&node.attrs) {
+            return;
+        }
+
+        let items = self.documented_impl_items(node);
+
+        // 出力するアイテムのない inherent impl は省略する
+        if node.trait_.is_none() && items.is_empty() && !has_docs(&node.attrs) {
+            return;
+        }
+
+        let self_type = extract_type_name(&node.self_ty);
+        match &node.trait_ {
+            Some((negative, trait_path, _)) => self.content.push_str(&format!(
+                "{} {}impl {}{} for {}\n\n",
+                heading,
+                if node.unsafety.is_some() {
+                    "unsafe "
+                } else {
+                    ""
+                },
+                if negative.is_some() { "!" } else { "" },
+                extract_path_with_generics(trait_path),
+                self_type
+            )),
+            None => self
+                .content
+                .push_str(&format!("{} impl {}\n\n", heading, self_type)),
+        }
+        self.push_impl_header(node);
+        // トレイトの実装はモデルでは `<Owner as Trait>::name` のパスで区別する
+        let model_owner = match &node.trait_ {
+            Some((_, trait_path, _)) => {
+                format!("<{} as {}>", owner, extract_path_with_generics(trait_path))
+            }
+            None => owner.to_string(),
+        };
+        self.push_impl_items(&items, &format!("{}#", heading), owner, &model_owner);
+    }
+
+    /// impl ブロックのヘッダー（利用条件・impl 宣言・docs）を出力する
+    fn push_impl_header(&mut self, node: &ItemImpl) {
+        self.push_doc_cfg_badge(&node.attrs);
+
+        self.content.push_str("```rust\n");
+        self.content.push_str(&format_impl_header(node));
+        self.content.push_str("\n```\n\n");
+
+        // impl ブロックのdocsコメントがあれば抽出
+        if has_docs(&node.attrs) {
+            self.extract_docs_for_item(&node.attrs);
+        }
+    }
+
+    /// impl ブロックの関連アイテムを `{owner}::{name}` のエントリとして出力し、
+    /// モデルには `{model_owner}::{name}` のパスで記録する
+    fn push_impl_items(
+        &mut self,
+        items: &[&syn::ImplItem],
+        heading: &str,
+        owner: &str,
+        model_owner: &str,
+    ) {
+        for item in items {
+            let (kind, name, signature, attrs) = match item {
+                syn::ImplItem::Fn(method) => (
+                    ItemKind::Method,
+                    &method.sig.ident,
+                    format_function_signature(&method.sig, &format_visibility(&method.vis), ""),
+                    &method.attrs,
+                ),
+                syn::ImplItem::Const(const_item) => (
+                    ItemKind::AssociatedConst,
+                    &const_item.ident,
+                    format!(
+                        "{}const {}: {};",
+                        format_visibility(&const_item.vis),
+                        const_item.ident,
+                        extract_type_name(&const_item.ty)
+                    ),
+                    &const_item.attrs,
+                ),
+                syn::ImplItem::Type(type_item) => {
+                    let mut signature = format!(
+                        "{}type {}",
+                        format_visibility(&type_item.vis),
+                        type_item.ident
+                    );
+                    signature.push_str(&format_generics(&type_item.generics));
+                    signature.push_str(&format!(" = {}", extract_type_name(&type_item.ty)));
+                    signature.push_str(&format_where_clause(&type_item.generics, ""));
+                    signature.push(';');
+                    (
+                        ItemKind::AssociatedType,
+                        &type_item.ident,
+                        signature,
+                        &type_item.attrs,
+                    )
+                }
+                _ => continue,
+            };
+
+            self.content
+                .push_str(&format!("{} {}::{}\n\n", heading, owner, name));
+            self.push_doc_cfg_badge(attrs);
+            self.content.push_str("```rust\n");
+            self.content.push_str(&signature);
+            self.content.push_str("\n```\n\n");
+            self.extract_docs_for_item(attrs);
+            self.record_item(
+                kind,
+                &format!("{}::{}", model_owner, name),
+                signature,
+                attrs,
+                item.span(),
+            );
+        }
+    }
+
+    /// `#[doc(cfg(..))]` があれば利用条件のバッジを出力する
+    fn push_doc_cfg_badge(&mut self, attrs: &[Attribute]) {
+        if let Some(badge) = format_doc_cfg_badge(attrs) {
+            self.content.push_str(&format!("*{}*\n\n", badge));
+        }
+    }
+
+    pub fn extract_docs_for_item(&mut self, attrs: &[syn::Attribute]) {
+        let lines = self.doc_text_lines(attrs);
+        let mut lines = lines.iter();
+        while let Some(line) = lines.next() {
+            let Some((fence, info)) = parse_fence(line) else {
+                self.content.push_str(&adjust_doc_heading(line));
+                self.content.push('\n');
+                continue;
+            };
+
+            // コードブロック内は見出しとして扱わず、Rust のコードは rustdoc と同じく隠し行を除く
+            self.content.push_str(line);
+            self.content.push('\n');
+            let mut code = Vec::new();
+            let mut closing = None;
+            for line in lines.by_ref() {
+                if is_closing_fence(line, fence) {
+                    closing = Some(line);
+                    break;
+                }
+                code.push(line.as_str());
+            }
+            let code = if is_rust_block(info) {
+                strip_hidden_lines(&code)
+            } else {
+                code.join("\n")
+            };
+            if !code.is_empty() {
+                self.content.push_str(&code);
+                self.content.push('\n');
+            }
+            if let Some(closing) = closing {
+                self.content.push_str(closing);
+                self.content.push('\n');
+            }
+        }
+        self.content.push('\n');
+    }
+
+    /// モデルに記録する docs（見出しレベルは調整しない）
+    fn item_docs(&self, attrs: &[syn::Attribute]) -> Option<String> {
+        let docs = self.doc_text_lines(attrs).join("\n");
+        let docs = docs.trim();
+        (!docs.is_empty()).then(|| docs.to_string())
+    }
+
+    /// `#[doc = ".."]` と `#[doc = include_str!("..")]` の内容を行ごとに返す
+    fn doc_text_lines(&self, attrs: &[syn::Attribute]) -> Vec<String> {
+        doc_text_lines(attrs, self.source_dir.as_deref(), true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::visit::Visit;
+
+    #[test]
+    fn test_extract_pattern_name() {
+        // Test different pattern types by constructing them manually
+        use syn::{PatIdent, PatWild};
+
+        // Test identifier pattern
+        let ident = syn::Ident::new("x", proc_macro2::Span::call_site());
         let pat_ident = PatIdent {
             attrs: vec![],
             by_ref: None,
@@ -1571,11 +2962,10 @@ mod tests {
         let ty: syn::Type = syn::parse_str(code).unwrap();
         assert_eq!(extract_type_name(&ty), "[u8]");
 
-        // Test array type (note: syn might parse const generics differently)
+        // Test array type
         let code = "[u8; 32]";
         let ty: syn::Type = syn::parse_str(code).unwrap();
-        let result = extract_type_name(&ty);
-        assert!(result.starts_with("[u8; ") && result.ends_with("]"));
+        assert_eq!(extract_type_name(&ty), "[u8; 32]");
 
         // Test tuple type
         let code = "(String, i32)";
@@ -1583,15 +2973,49 @@ mod tests {
         assert_eq!(extract_type_name(&ty), "(String, i32)");
     }
 
+    #[test]
+    fn test_extract_type_name_complete() {
+        // ソースと同じ表記に戻ることを確認する
+        let cases = [
+            "[u8; N * 2]",
+            "[[f32; 4]; SIZE]",
+            "impl Iterator<Item = u32> + Send + 'a",
+            "Box<dyn Fn(u8) -> bool + Send + Sync>",
+            "&'a mut dyn FnMut(&str, usize)",
+            "Array<u8, 32>",
+            "Buf<{ N + 1 }>",
+            "Matrix<{ -1 }>",
+            "<T as Iterator>::Item",
+            "<Self as Trait<u8>>::Output<'a>",
+            "<[T]>::Owned",
+            "::std::fmt::Result",
+            "fn(i32) -> i32",
+            "for<'a> unsafe extern \"C\" fn(data: &'a u8, ...) -> i32",
+            "extern fn()",
+            "Option<!>",
+            "(u8,)",
+            "()",
+            "*const [u8]",
+            "Vec<_>",
+            "Box<dyn Iterator<Item: Clone + Send>>",
+            "Pin<&mut (dyn Future<Output = ()> + Send)>",
+            "GAT<Assoc<'a> = &'a str>",
+            "ConstAssoc<N = 4>",
+            "vec_type!(u8)",
+        ];
+        for code in cases {
+            let ty: syn::Type = syn::parse_str(code).unwrap();
+            assert_eq!(extract_type_name(&ty), code);
+        }
+    }
+
     #[test]
     fn test_extract_where_clause() {
         let code = "fn test<T>() where T: Clone + Send, T: 'static {}";
         let item: syn::ItemFn = syn::parse_str(code).unwrap();
 
-        if let Some(where_clause) = &item.sig.generics.where_clause {
-            let result = extract_where_clause(where_clause);
-            assert!(result.contains("T: Clone + Send"));
-        }
+        let result = format_where_clause(&item.sig.generics, "");
+        assert_eq!(result, "\nwhere\n    T: Clone + Send,\n    T: 'static");
     }
 
     #[test]
@@ -1620,10 +3044,7 @@ mod tests {
 
         let file: syn::File = syn::parse_str(code).unwrap();
         let mut items = Vec::new();
-        let mut visitor = TocVisitor {
-            items: &mut items,
-            current_mod: Vec::new(),
-        };
+        let mut visitor = TocVisitor::new(&mut items);
 
         visitor.visit_file(&file);
 
@@ -1631,6 +3052,30 @@ mod tests {
         assert_eq!(items[0], "pub fn public_function");
     }
 
+    #[test]
+    fn test_toc_visitor_foreign_functions() {
+        let code = r#"
+            unsafe extern "C" {
+                pub fn printf(format: *const c_char, ...) -> c_int;
+            }
+            extern {
+                pub fn legacy();
+            }
+        "#;
+
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut items = Vec::new();
+        let mut visitor = TocVisitor::new(&mut items);
+
+        visitor.visit_file(&file);
+
+        // llms-full.txt の extern ブロックと同じ修飾子・ABI を使う
+        assert_eq!(
+            items,
+            vec!["pub unsafe extern \"C\" fn printf", "pub extern fn legacy"]
+        );
+    }
+
     #[test]
     fn test_toc_visitor_struct() {
         let code = r#"
@@ -1640,10 +3085,7 @@ mod tests {
 
         let file: syn::File = syn::parse_str(code).unwrap();
         let mut items = Vec::new();
-        let mut visitor = TocVisitor {
-            items: &mut items,
-            current_mod: Vec::new(),
-        };
+        let mut visitor = TocVisitor::new(&mut items);
 
         visitor.visit_file(&file);
 
@@ -1665,192 +3107,705 @@ mod tests {
 
         let file: syn::File = syn::parse_str(code).unwrap();
         let mut items = Vec::new();
-        let mut visitor = TocVisitor {
-            items: &mut items,
-            current_mod: Vec::new(),
+        let mut visitor = TocVisitor::new(&mut items);
+
+        visitor.visit_file(&file);
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0], "pub enum PublicEnum");
+    }
+
+    #[test]
+    fn test_toc_visitor_with_module() {
+        let code = r#"
+            pub mod submodule {
+                pub fn function_in_module() {}
+                pub struct StructInModule;
+            }
+        "#;
+
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut items = Vec::new();
+        let mut visitor = TocVisitor::new(&mut items);
+
+        visitor.visit_file(&file);
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0], "pub mod submodule");
+        assert_eq!(items[1], "pub fn submodule::function_in_module");
+        assert_eq!(items[2], "pub struct submodule::StructInModule");
+    }
+
+    #[test]
+    fn test_summary_visitor() {
+        let code = r#"
+            /// This is a public function
+            pub fn test_function(param: u32) -> String {
+                String::new()
+            }
+            
+            /// This is a public struct
+            pub struct TestStruct {
+                pub field: i32,
+            }
+        "#;
+
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut public_count = 0;
+        let mut types = Vec::new();
+
+        let mut visitor = SummaryVisitor {
+            public_count: &mut public_count,
+            types: &mut types,
+        };
+
+        visitor.visit_file(&file);
+
+        assert_eq!(public_count, 2);
+        assert!(types.contains(&"functions".to_string()));
+        assert!(types.contains(&"structs".to_string()));
+    }
+
+    #[test]
+    fn test_complete_docs_visitor_function_with_generics_and_where() {
+        let code = r#"
+            /// A generic function with where clause
+            pub fn generic_function<T, U>(param1: T, param2: U) -> T 
+            where 
+                T: Clone + Send,
+                U: std::fmt::Display,
+            {
+                param1
+            }
+        "#;
+
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut content = String::new();
+
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+
+        visitor.visit_file(&file);
+
+        assert!(content.contains("generic_function<T, U>"));
+        assert!(content.contains("param1: T"));
+        assert!(content.contains("param2: U"));
+        assert!(content.contains("T: Clone + Send"));
+        assert!(content.contains("U: std::fmt::Display"));
+    }
+
+    #[test]
+    fn test_extract_path_with_generics() {
+        // Test simple path without generics
+        let code = "std::fmt::Display";
+        let path: syn::Path = syn::parse_str(code).unwrap();
+        assert_eq!(extract_path_with_generics(&path), "std::fmt::Display");
+
+        // Test path with type generics
+        let code = "Vec<T>";
+        let path: syn::Path = syn::parse_str(code).unwrap();
+        assert_eq!(extract_path_with_generics(&path), "Vec<T>");
+
+        // Test path with multiple generics
+        let code = "HashMap<String, i32>";
+        let path: syn::Path = syn::parse_str(code).unwrap();
+        assert_eq!(extract_path_with_generics(&path), "HashMap<String, i32>");
+
+        // Test path with associated type
+        let code = "Iterator<Item = String>";
+        let path: syn::Path = syn::parse_str(code).unwrap();
+        let result = extract_path_with_generics(&path);
+        assert_eq!(result, "Iterator<Item = String>");
+
+        // Test nested generics
+        let code = "Option<Vec<T>>";
+        let path: syn::Path = syn::parse_str(code).unwrap();
+        assert_eq!(extract_path_with_generics(&path), "Option<Vec<T>>");
+    }
+
+    #[test]
+    fn test_complete_docs_visitor_enum_with_variants() {
+        let code = r#"
+            /// An enum with different variant types
+            pub enum TestEnum {
+                /// Unit variant
+                Unit,
+                /// Tuple variant
+                Tuple(String, i32),
+                Empty(),
+                /// Struct variant  
+                Struct { name: String, value: i32 },
+                #[cfg(feature = "test")]
+                ConditionalVariant,
+            }
+        "#;
+
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut content = String::new();
+
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+
+        visitor.visit_file(&file);
+
+        assert!(content.contains("pub enum TestEnum"));
+        assert!(content.contains("Unit"));
+        assert!(content.contains("Tuple(String, i32)"));
+        assert!(content.contains("    Empty(),\n"));
+        assert!(content.contains("Struct { name: String, value: i32 }"));
+        assert!(content.contains(r#"#[cfg(feature = "test")]"#));
+    }
+
+    #[test]
+    fn test_complete_docs_visitor_member_docs() {
+        let code = r#"
+            #[derive(Clone, Copy)]
+            #[repr(u8)]
+            pub enum Level {
+                /// Verbose output.
+                ///
+                /// Only for debugging.
+                Trace = 1,
+                Info = 1 << 2,
+                /// A located message.
+                At(
+                    /// Line number.
+                    u32,
+                    String,
+                ),
+                Span {
+                    /// First line.
+                    start: u32,
+                    end: u32,
+                },
+            }
+
+            #[repr(C, packed(2))]
+            pub struct Header {
+                /// Format version.
+                ///
+                /// ```
+                /// if header.version > 1 {
+                ///     upgrade();
+                /// }
+                /// ```
+                pub version: u16,
+                len: u32,
+            }
+
+            pub struct Meters(
+                /// Distance in meters.
+                pub f64,
+            );
+        "#;
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.visit_file(&file);
+
+        assert!(content.contains(
+            "#[derive(Clone, Copy)]\n#[repr(u8)]\npub enum Level {\n    \
+             /// Verbose output.\n    ///\n    /// Only for debugging.\n    Trace = 1,\n    \
+             Info = 1 << 2,\n    \
+             /// A located message.\n    At(\n        /// Line number.\n        u32,\n        String,\n    ),\n    \
+             Span {\n        /// First line.\n        start: u32,\n        end: u32,\n    },\n}"
+        ));
+        // 非公開のフィールドとその docs は出力しない
+        assert!(content.contains(
+            "#[repr(C, packed(2))]\npub struct Header {\n    /// Format version.\n    ///\n    /// ```\n    \
+             /// if header.version > 1 {\n    ///     upgrade();\n    /// }\n    /// ```\n    pub version: u16,\n}"
+        ));
+        assert!(
+            content.contains("pub struct Meters(\n    /// Distance in meters.\n    pub f64,\n);")
+        );
+    }
+
+    #[test]
+    fn test_complete_docs_visitor_impl_block() {
+        let code = r#"
+            pub struct TestStruct;
+            
+            impl TestStruct {
+                /// A public method
+                pub fn public_method(&self, param: u32) -> String {
+                    String::new()
+                }
+                
+                fn private_method(&self) {}
+            }
+        "#;
+
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut content = String::new();
+
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+
+        visitor.visit_file(&file);
+
+        assert!(content.contains("impl TestStruct"));
+        assert!(content.contains("pub fn public_method"));
+        assert!(content.contains("param: u32"));
+        assert!(!content.contains("private_method"));
+    }
+
+    #[test]
+    fn test_toc_visitor_visibility_levels() {
+        let code = r#"
+            pub fn public_function() {}
+            pub(crate) fn crate_function() {}
+            fn private_function() {}
+            mod inner {
+                pub(super) struct SuperStruct;
+                pub(in crate::inner) fn restricted_function() {}
+            }
+        "#;
+        let file: syn::File = syn::parse_str(code).unwrap();
+
+        let mut items = Vec::new();
+        let mut visitor = TocVisitor::new(&mut items);
+        visitor.visibility = VisibilityLevel::Crate;
+        visitor.visit_file(&file);
+        assert_eq!(
+            items,
+            vec![
+                "pub fn public_function",
+                "pub(crate) fn crate_function",
+                "pub(super) struct inner::SuperStruct",
+                "pub(in crate::inner) fn inner::restricted_function",
+            ]
+        );
+
+        let mut items = Vec::new();
+        let mut visitor = TocVisitor::new(&mut items);
+        visitor.visibility = VisibilityLevel::All;
+        visitor.visit_file(&file);
+        assert!(items.contains(&"fn private_function".to_string()));
+        assert!(items.contains(&"mod inner".to_string()));
+    }
+
+    #[test]
+    fn test_complete_docs_visitor_visibility_levels() {
+        let code = r#"
+            pub struct Config {
+                pub name: String,
+                pub(crate) cache: Vec<u8>,
+                secret: u32,
+            }
+
+            impl Config {
+                pub fn new() -> Self { todo!() }
+                pub(crate) fn reset(&mut self) {}
+                fn validate(&self) -> bool { true }
+            }
+        "#;
+        let file: syn::File = syn::parse_str(code).unwrap();
+
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.visibility = VisibilityLevel::Crate;
+        visitor.visit_file(&file);
+        assert!(content.contains("    pub(crate) cache: Vec<u8>,"));
+        assert!(!content.contains("secret"));
+        assert!(content.contains("pub(crate) fn reset(&mut self)"));
+        assert!(!content.contains("validate"));
+
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.visibility = VisibilityLevel::All;
+        visitor.visit_file(&file);
+        assert!(content.contains("    secret: u32,"));
+        assert!(content.contains("```rust\nfn validate(&self) -> bool\n```"));
+    }
+
+    #[test]
+    fn test_is_doc_hidden() {
+        let item: syn::ItemFn = syn::parse_quote! {
+            #[doc(hidden)]
+            pub fn hidden() {}
+        };
+        assert!(is_doc_hidden(&item.attrs));
+
+        let item: syn::ItemFn = syn::parse_quote! {
+            #[cfg_attr(not(test), doc(hidden))]
+            pub fn conditionally_hidden() {}
+        };
+        assert!(is_doc_hidden(&item.attrs));
+
+        let item: syn::ItemFn = syn::parse_quote! {
+            /// Visible
+            #[doc(alias = "shown")]
+            pub fn visible() {}
+        };
+        assert!(!is_doc_hidden(&item.attrs));
+    }
+
+    #[test]
+    fn test_format_doc_cfg_badge() {
+        let item: syn::ItemFn = syn::parse_quote! {
+            #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+            pub fn to_json() {}
+        };
+        assert_eq!(
+            format_doc_cfg_badge(&item.attrs).as_deref(),
+            Some("Available on crate feature `serde` only.")
+        );
+
+        let item: syn::ItemFn = syn::parse_quote! {
+            #[doc(cfg(all(unix, target_pointer_width = "64")))]
+            pub fn unix_only() {}
         };
+        assert_eq!(
+            format_doc_cfg_badge(&item.attrs).as_deref(),
+            Some("Available on `all(unix, target_pointer_width = \"64\")` only.")
+        );
+
+        let item: syn::ItemFn = syn::parse_quote! {
+            pub fn always() {}
+        };
+        assert_eq!(format_doc_cfg_badge(&item.attrs), None);
+    }
+
+    #[test]
+    fn test_complete_docs_visitor_doc_hidden() {
+        let code = r#"
+            pub struct Visible {
+                pub name: String,
+                #[doc(hidden)]
+                pub __private: (),
+            }
+
+            #[doc(hidden)]
+            pub fn hidden_function() {}
+
+            #[doc(hidden)]
+            pub mod hidden_module {
+                pub fn inside_hidden() {}
+            }
+
+            pub enum Kind {
+                A,
+                #[doc(hidden)]
+                __Nonexhaustive,
+            }
+        "#;
+        let file: syn::File = syn::parse_str(code).unwrap();
 
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
         visitor.visit_file(&file);
+        assert!(content.contains("pub name: String"));
+        assert!(!content.contains("__private"));
+        assert!(!content.contains("hidden_function"));
+        assert!(!content.contains("inside_hidden"));
+        assert!(!content.contains("__Nonexhaustive"));
 
-        assert_eq!(items.len(), 1);
-        assert_eq!(items[0], "pub enum PublicEnum");
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.include_hidden = true;
+        visitor.visit_file(&file);
+        assert!(content.contains("pub __private: ()"));
+        assert!(content.contains("### hidden_function"));
+        assert!(content.contains("__Nonexhaustive"));
     }
 
     #[test]
-    fn test_toc_visitor_with_module() {
-        let code = r#"
-            pub mod submodule {
-                pub fn function_in_module() {}
-                pub struct StructInModule;
+    fn test_extract_docs_include_str() {
+        let temp_dir = std::env::temp_dir().join("cargo_llms_txt_test_include_str");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(temp_dir.join("src")).unwrap();
+        std::fs::write(temp_dir.join("docs.md"), "# Engine\n\nDrives everything.\n").unwrap();
+
+        let item: syn::ItemStruct = syn::parse_quote! {
+            #[doc = include_str!("../docs.md")]
+            pub struct Engine {
+                #[doc = include_str!("../docs.md")]
+                pub power: u32,
             }
-        "#;
-
-        let file: syn::File = syn::parse_str(code).unwrap();
-        let mut items = Vec::new();
-        let mut visitor = TocVisitor {
-            items: &mut items,
-            current_mod: Vec::new(),
         };
 
-        visitor.visit_file(&file);
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.source_dir = Some(temp_dir.join("src"));
+        visitor.visit_item_struct(&item);
 
-        assert_eq!(items.len(), 3);
-        assert_eq!(items[0], "pub mod submodule");
-        assert_eq!(items[1], "pub fn submodule::function_in_module");
-        assert_eq!(items[2], "pub struct submodule::StructInModule");
+        assert!(content.contains("#### Engine\n\nDrives everything.\n"));
+        assert!(content.contains(
+            "    /// # Engine\n    ///\n    /// Drives everything.\n    pub power: u32,"
+        ));
+
+        std::fs::remove_dir_all(&temp_dir).unwrap();
     }
 
     #[test]
-    fn test_summary_visitor() {
+    fn test_extract_docs_code_blocks() {
         let code = r#"
-            /// This is a public function
-            pub fn test_function(param: u32) -> String {
-                String::new()
-            }
-            
-            /// This is a public struct
-            pub struct TestStruct {
-                pub field: i32,
-            }
+            /// Adds numbers.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// # use rv::add;
+            /// ## not hidden
+            /// if add(1, 2) == 3 {
+            ///     println!("ok");
+            /// }
+            /// ```
+            ///
+            /// ```text
+            /// # kept as is
+            /// ```
+            pub fn add(a: i32, b: i32) -> i32 { a + b }
         "#;
 
         let file: syn::File = syn::parse_str(code).unwrap();
-        let mut public_count = 0;
-        let mut types = Vec::new();
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.visit_file(&file);
 
-        let mut visitor = SummaryVisitor {
-            public_count: &mut public_count,
-            types: &mut types,
-        };
+        assert!(content.contains(
+            "#### Examples\n\n```\n# not hidden\nif add(1, 2) == 3 {\n    println!(\"ok\");\n}\n```\n"
+        ));
+        assert!(content.contains("```text\n# kept as is\n```\n"));
+        assert!(!content.contains("use rv::add"));
+    }
 
-        visitor.visit_file(&file);
+    #[test]
+    fn test_doc_summary() {
+        let file: syn::File =
+            syn::parse_str("//! First line\n//! continues here.\n//!\n//! Second paragraph.\n")
+                .unwrap();
+        assert_eq!(
+            doc_summary(&file.attrs, None).as_deref(),
+            Some("First line continues here.")
+        );
 
-        assert_eq!(public_count, 2);
-        assert!(types.contains(&"functions".to_string()));
-        assert!(types.contains(&"structs".to_string()));
+        let file: syn::File = syn::parse_str("pub fn undocumented() {}").unwrap();
+        assert_eq!(doc_summary(&file.attrs, None), None);
     }
 
     #[test]
-    fn test_complete_docs_visitor_function_with_generics_and_where() {
+    fn test_complete_docs_visitor_nested_inline_modules() {
         let code = r#"
-            /// A generic function with where clause
-            pub fn generic_function<T, U>(param1: T, param2: U) -> T 
-            where 
-                T: Clone + Send,
-                U: std::fmt::Display,
-            {
-                param1
+            pub mod zeta {
+                pub struct Item;
             }
-        "#;
 
-        let file: syn::File = syn::parse_str(code).unwrap();
-        let mut content = String::new();
+            pub mod alpha {
+                //! Alpha module
+                pub struct Item;
 
-        let mut visitor = CompleteDocsVisitor {
-            content: &mut content,
-            current_mod: Vec::new(),
-        };
+                pub mod inner {
+                    pub fn nested() {}
+                }
+            }
+
+            pub fn root_function() {}
+        "#;
+        let file: syn::File = syn::parse_str(code).unwrap();
 
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
         visitor.visit_file(&file);
 
-        assert!(content.contains("generic_function<T, U>"));
-        assert!(content.contains("param1: T"));
-        assert!(content.contains("param2: U"));
-        assert!(content.contains("T: Clone + Send"));
-        assert!(content.contains("U: std::fmt::Display"));
+        // 同名のアイテムはモジュールパスで区別される
+        assert!(content.contains("### zeta::Item\n"));
+        assert!(content.contains("### alpha::Item\n"));
+        assert!(content.contains("### alpha::inner::nested\n"));
+        assert!(content.contains("## mod alpha\n\nAlpha module\n"));
+
+        // ルートのアイテムの後にモジュールツリーの順でセクションが続く
+        let positions: Vec<usize> = [
+            "## crate",
+            "### root_function",
+            "## mod alpha\n",
+            "## mod alpha::inner\n",
+            "## mod zeta\n",
+        ]
+        .iter()
+        .map(|heading| content.find(heading).unwrap())
+        .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
     }
 
     #[test]
-    fn test_extract_path_with_generics() {
-        // Test simple path without generics
-        let code = "std::fmt::Display";
-        let path: syn::Path = syn::parse_str(code).unwrap();
-        assert_eq!(extract_path_with_generics(&path), "std::fmt::Display");
+    fn test_complete_docs_visitor_impl_items_under_type() {
+        let code = r#"
+            /// A counter
+            pub struct Counter;
 
-        // Test path with type generics
-        let code = "Vec<T>";
-        let path: syn::Path = syn::parse_str(code).unwrap();
-        assert_eq!(extract_path_with_generics(&path), "Vec<T>");
+            pub fn unrelated() {}
 
-        // Test path with multiple generics
-        let code = "HashMap<String, i32>";
-        let path: syn::Path = syn::parse_str(code).unwrap();
-        assert_eq!(extract_path_with_generics(&path), "HashMap<String, i32>");
+            impl Counter {
+                /// Largest value the counter can hold
+                pub const MAX: u64 = 100;
 
-        // Test path with associated type
-        let code = "Iterator<Item = String>";
-        let path: syn::Path = syn::parse_str(code).unwrap();
-        let result = extract_path_with_generics(&path);
-        assert_eq!(result, "Iterator<Item = String>");
+                /// Creates a counter
+                pub fn new() -> Self { Counter }
 
-        // Test nested generics
-        let code = "Option<Vec<T>>";
-        let path: syn::Path = syn::parse_str(code).unwrap();
-        assert_eq!(extract_path_with_generics(&path), "Option<Vec<T>>");
-    }
+                fn internal(&self) {}
+            }
 
-    #[test]
-    fn test_complete_docs_visitor_enum_with_variants() {
-        let code = r#"
-            /// An enum with different variant types
-            pub enum TestEnum {
-                /// Unit variant
-                Unit,
-                /// Tuple variant
-                Tuple(String, i32),
-                /// Struct variant  
-                Struct { name: String, value: i32 },
-                #[cfg(feature = "test")]
-                ConditionalVariant,
+            impl Iterator for Counter {
+                /// Yielded values
+                type Item = u64;
+                fn next(&mut self) -> Option<u64> { None }
             }
         "#;
+        let module_file = crate::modules::ModuleFile {
+            relative_path: Default::default(),
+            module_path: Vec::new(),
+            kind: crate::modules::TargetKind::Lib,
+            doc_hidden: false,
+            syntax: syn::parse_str(code).unwrap(),
+        };
+        let index = ImplIndex::build(Path::new(""), [&module_file]);
 
-        let file: syn::File = syn::parse_str(code).unwrap();
         let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.impls = Some(&index);
+        visitor.visit_file(&module_file.syntax);
+
+        // 関連アイテムは型のエントリの下に個別に出力される
+        assert!(content.contains(
+            "#### Methods\n\n```rust\nimpl Counter\n```\n\n##### Counter::MAX\n\n```rust\npub const MAX: u64;\n```\n\nLargest value the counter can hold\n"
+        ));
+        assert!(content.contains(
+            "##### Counter::new\n\n```rust\npub fn new() -> Self\n```\n\nCreates a counter\n"
+        ));
+        assert!(content.contains(
+            "#### Trait Implementations\n\n##### impl Iterator for Counter\n\n```rust\nimpl Iterator for Counter\n```\n\n###### Counter::Item\n\n```rust\ntype Item = u64;\n```\n\nYielded values\n"
+        ));
+        assert!(content.contains("###### Counter::next"));
+        assert!(!content.contains("internal"));
+        assert!(!content.contains("\n### impl"));
+
+        let counter = content.find("### Counter").unwrap();
+        let unrelated = content.find("### unrelated").unwrap();
+        let methods = content.find("#### Methods").unwrap();
+        let traits = content.find("#### Trait Implementations").unwrap();
+        assert!(counter < methods && methods < traits && traits < unrelated);
+    }
 
-        let mut visitor = CompleteDocsVisitor {
-            content: &mut content,
-            current_mod: Vec::new(),
-        };
+    #[test]
+    fn test_complete_docs_visitor_records_items() {
+        let code = r#"
+            //! Crate docs
 
-        visitor.visit_file(&file);
+            /// Parses input
+            #[cfg(feature = "parse")]
+            pub fn parse(input: &str) -> usize { 0 }
 
-        assert!(content.contains("pub enum TestEnum"));
-        assert!(content.contains("Unit"));
-        assert!(content.contains("Tuple(String, i32)"));
-        assert!(content.contains("Struct { name: String, value: i32 }"));
-        assert!(content.contains(r#"#[cfg(feature = "test")]"#));
+            pub use std::collections::HashMap;
+        "#;
+        let syntax: syn::File = syn::parse_str(code).unwrap();
+
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.source_file = PathBuf::from("src/lib.rs");
+        visitor.render_module(&syntax.attrs, &syntax.items, true);
+
+        assert_eq!(visitor.module_docs.as_deref(), Some("Crate docs"));
+        assert_eq!(visitor.items.len(), 2);
+
+        let parse = &visitor.items[0];
+        assert_eq!(parse.kind, ItemKind::Function);
+        assert_eq!(parse.path, "parse");
+        assert_eq!(parse.signature, "pub fn parse(input: &str) -> usize");
+        assert_eq!(parse.docs.as_deref(), Some("Parses input"));
+        assert_eq!(parse.cfg, vec!["feature = \"parse\""]);
+        let span = parse.span.as_ref().unwrap();
+        assert_eq!(span.file, "src/lib.rs");
+        assert_eq!((span.start_line, span.end_line), (4, 6));
+
+        let reexport = &visitor.items[1];
+        assert_eq!(reexport.kind, ItemKind::ReExport);
+        assert_eq!(reexport.signature, "pub use std::collections::HashMap;");
+        assert_eq!(reexport.docs, None);
     }
 
     #[test]
-    fn test_complete_docs_visitor_impl_block() {
+    fn test_reexport_headings_use_bound_names() {
         let code = r#"
-            pub struct TestStruct;
-            
-            impl TestStruct {
-                /// A public method
-                pub fn public_method(&self, param: u32) -> String {
-                    String::new()
-                }
-                
-                fn private_method(&self) {}
+            pub mod a {
+                pub use std::fmt::Debug;
+                pub use super::internal::{self, Other as Renamed};
             }
+            pub mod x {
+                pub use std::fmt::Debug;
+                pub extern crate core as corex;
+                pub use std::io::*;
+            }
+            pub extern crate alloc;
         "#;
-
         let file: syn::File = syn::parse_str(code).unwrap();
         let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.visit_file(&file);
 
-        let mut visitor = CompleteDocsVisitor {
-            content: &mut content,
-            current_mod: Vec::new(),
-        };
+        assert!(content.contains("### a::Debug\n\n```rust\npub use std::fmt::Debug;\n```"));
+        assert!(content.contains("### x::Debug\n\n```rust\npub use std::fmt::Debug;\n```"));
+        assert!(content.contains(
+            "### a::{internal, Renamed}\n\n```rust\npub use super::internal::{self, Other as Renamed};"
+        ));
+        assert!(content.contains("### x::corex\n\n```rust\npub extern crate core as corex;\n```"));
+        assert!(content.contains("### x::*\n\n```rust\npub use std::io::*;"));
+        assert!(content.contains("### alloc\n\n```rust\npub extern crate alloc;\n```"));
 
-        visitor.visit_file(&file);
+        let mut items = Vec::new();
+        TocVisitor::new(&mut items).visit_file(&file);
+        assert!(items.contains(&"pub use a::Debug".to_string()));
+        assert!(items.contains(&"pub use a::{internal, Renamed}".to_string()));
+        assert!(items.contains(&"pub use x::Debug".to_string()));
+        assert!(items.contains(&"pub extern crate x::corex".to_string()));
+        assert!(items.contains(&"pub extern crate alloc".to_string()));
+    }
 
-        assert!(content.contains("impl TestStruct"));
-        assert!(content.contains("pub fn public_method"));
-        assert!(content.contains("param: u32"));
-        assert!(!content.contains("private_method"));
+    #[test]
+    fn test_complete_docs_visitor_derived_and_blanket_impls() {
+        let code = r#"
+            pub trait Describe {}
+            impl<T: Clone + ?Sized> Describe for T {}
+
+            pub trait Render {}
+            impl<T> Render for T where T: std::fmt::Display {}
+
+            #[derive(Debug, Clone, serde::Serialize)]
+            pub struct Wrapper<'a, T> { pub value: &'a T }
+
+            #[derive(Debug)]
+            pub struct Buffer<'a, T: Clone + 'a, const N: usize = 4> where T: Default {
+                pub data: [&'a T; N],
+            }
+        "#;
+        let module_file = crate::modules::ModuleFile {
+            relative_path: Default::default(),
+            module_path: Vec::new(),
+            kind: crate::modules::TargetKind::Lib,
+            doc_hidden: false,
+            syntax: syn::parse_str(code).unwrap(),
+        };
+        let index = ImplIndex::build(Path::new(""), [&module_file]);
+
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.impls = Some(&index);
+        visitor.visit_file(&module_file.syntax);
+
+        assert!(content.contains("#[derive(Debug, Clone, serde::Serialize)]"));
+        assert!(content.contains(
+            "#### Trait Implementations\n\n\
+             - `impl<'a, T> Debug for Wrapper<'a, T>` (derived)\n\
+             - `impl<'a, T> Clone for Wrapper<'a, T>` (derived)\n\
+             - `impl<'a, T> serde::Serialize for Wrapper<'a, T>` (derived)\n\
+             - `impl<T: Clone + ?Sized> Describe for T` (blanket implementation)\n\n"
+        ));
+        // 境界を満たさないブランケット実装は含めない
+        assert!(!content.contains("Render for T where T: std::fmt::Display` (blanket"));
+        // ブランケット実装自体は定義位置にも出力される
+        assert!(content.contains("### impl Describe for T"));
+        assert!(!content.contains("#### Methods"));
+        // impl の宣言には境界と const を含め、デフォルト値は省く
+        assert!(content.contains(
+            "- `impl<'a, T: Clone + 'a, const N: usize> Debug for Buffer<'a, T, N> where T: Default` (derived)\n"
+        ));
     }
 
     #[test]
@@ -1871,10 +3826,7 @@ mod tests {
         let file: syn::File = syn::parse_str(code).unwrap();
         let mut content = String::new();
 
-        let mut visitor = CompleteDocsVisitor {
-            content: &mut content,
-            current_mod: Vec::new(),
-        };
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
 
         visitor.visit_file(&file);
 
@@ -1885,27 +3837,52 @@ mod tests {
         assert!(content.contains("Deserialize<'de>") || content.contains("Deserialize"));
     }
 
+    #[test]
+    fn test_complete_docs_visitor_unsafe_qualifiers() {
+        let code = r#"
+            pub struct Handle;
+
+            unsafe impl Send for Handle {}
+
+            pub unsafe trait RawAccess {}
+
+            pub unsafe auto trait Marker {}
+        "#;
+
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut content = String::new();
+
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+
+        visitor.visit_file(&file);
+
+        // unsafe / auto 修飾子を落とさない
+        assert!(content.contains("unsafe impl Send for Handle"));
+        assert!(content.contains("pub unsafe trait RawAccess"));
+        assert!(content.contains("pub unsafe auto trait Marker"));
+    }
+
     #[test]
     fn test_format_function_signature() {
         // Test simple function without generics
         let sig: syn::Signature = syn::parse_str("fn simple_function(x: i32) -> bool").unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert_eq!(result, "fn simple_function(x: i32) -> bool");
 
         // Test with pub
-        let result = format_function_signature(&sig, true, "");
+        let result = format_function_signature(&sig, "pub ", "");
         assert_eq!(result, "pub fn simple_function(x: i32) -> bool");
 
         // Test function with generics
         let sig: syn::Signature =
             syn::parse_str("fn generic_function<T>(value: T) -> Option<T>").unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert_eq!(result, "fn generic_function<T>(value: T) -> Option<T>");
 
         // Test function with self parameter
         let sig: syn::Signature =
             syn::parse_str("fn method(&self, param: String) -> Result<(), Error>").unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert_eq!(
             result,
             "fn method(&self, param: String) -> Result<(), Error>"
@@ -1913,13 +3890,13 @@ mod tests {
 
         // Test function with mutable self
         let sig: syn::Signature = syn::parse_str("fn mut_method(&mut self, x: i32)").unwrap();
-        let result = format_function_signature(&sig, true, "");
+        let result = format_function_signature(&sig, "pub ", "");
         assert_eq!(result, "pub fn mut_method(&mut self, x: i32)");
 
         // Test function with multiple parameters
         let sig: syn::Signature =
             syn::parse_str("fn multi_param(a: i32, b: &str, c: Vec<u8>) -> String").unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert_eq!(
             result,
             "fn multi_param(a: i32, b: &str, c: Vec<u8>) -> String"
@@ -1927,21 +3904,201 @@ mod tests {
 
         // Test function with no parameters
         let sig: syn::Signature = syn::parse_str("fn no_params() -> bool").unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert_eq!(result, "fn no_params() -> bool");
 
         // Test function with no return type
         let sig: syn::Signature = syn::parse_str("fn no_return(x: i32)").unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert_eq!(result, "fn no_return(x: i32)");
     }
 
+    #[test]
+    fn test_format_function_signature_qualifiers() {
+        let cases = [
+            ("pub ", "pub const fn new() -> Self"),
+            ("pub ", "pub async fn fetch(url: &str) -> Result<String>"),
+            (
+                "pub ",
+                "pub const unsafe fn from_raw(ptr: *const u8) -> Self",
+            ),
+            ("", "async unsafe fn both()"),
+            (
+                "pub ",
+                "pub unsafe extern \"system\" fn callback(hwnd: isize) -> i32",
+            ),
+            ("", "extern fn implicit_c()"),
+            (
+                "",
+                "unsafe extern \"C\" fn printf(format: *const c_char, ...) -> c_int",
+            ),
+            (
+                "",
+                "fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()>",
+            ),
+            ("", "fn borrow<'a>(&'a mut self) -> &'a u8"),
+            (
+                "",
+                "fn point((x, y): (i32, i32), Point { x: px, .. }: Point, &v: &u8)",
+            ),
+            (
+                "",
+                "fn wrapper(Wrapper(inner): Wrapper, [first, ..]: [u8; 4])",
+            ),
+        ];
+        for (vis, code) in cases {
+            let sig: syn::Signature = syn::parse_str(code.trim_start_matches(vis)).unwrap();
+            assert_eq!(format_function_signature(&sig, vis, ""), code);
+        }
+
+        // 束縛の `mut` は省略する
+        let sig: syn::Signature =
+            syn::parse_str("fn consume(mut self, mut buf: Vec<u8>, args: ...)").unwrap();
+        assert_eq!(
+            format_function_signature(&sig, "", ""),
+            "fn consume(self, buf: Vec<u8>, args: ...)"
+        );
+    }
+
+    #[test]
+    fn test_complete_docs_visitor_fn_qualifiers() {
+        let code = r#"
+            pub trait Service {
+                async fn call(&self, req: Request) -> Response;
+                unsafe fn reset(self: Box<Self>);
+            }
+            unsafe extern "C" {
+                pub fn printf(format: *const c_char, ...) -> c_int;
+            }
+            #[no_mangle]
+            pub extern "system" fn on_event(code: u32) {}
+        "#;
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.visit_file(&file);
+
+        assert!(content.contains("    async fn call(&self, req: Request) -> Response;"));
+        assert!(content.contains("    unsafe fn reset(self: Box<Self>);"));
+        assert!(content.contains(
+            "unsafe extern \"C\" {\n    pub fn printf(format: *const c_char, ...) -> c_int;\n}"
+        ));
+        assert!(content.contains("#[no_mangle]\npub extern \"system\" fn on_event(code: u32)"));
+    }
+
+    #[test]
+    fn test_complete_docs_visitor_macro_rules() {
+        let code = r#"
+            /// Builds a map.
+            #[macro_export]
+            macro_rules! map {
+                () => { HashMap::new() };
+                ($($key:expr => $value:expr),+ $(,)?) => {{
+                    let mut map = HashMap::new();
+                    $(map.insert($key, $value);)+
+                    map
+                }};
+            }
+
+            macro_rules! internal {
+                [$name:ident; $($arg:tt)*] => { $name!($($arg)*) }
+            }
+
+            pub mod nested {
+                #[macro_export]
+                macro_rules! nested_export {
+                    ($x:expr) => { $x };
+                }
+            }
+        "#;
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.visit_file(&file);
+
+        assert!(content.contains(
+            "### map!\n\n```rust\n#[macro_export]\nmacro_rules! map {\n    \
+             () => { ... };\n    \
+             ($($key:expr => $value:expr),+ $(,)?) => { ... };\n}\n```\n\nBuilds a map."
+        ));
+        // `#[macro_export]` のマクロはクレートルートから公開される
+        assert!(content.contains("### nested_export!\n"));
+        // 公開APIの出力ではエクスポートされていないマクロは含めない
+        assert!(!content.contains("internal!"));
+
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.visibility = VisibilityLevel::Crate;
+        visitor.visit_file(&file);
+        assert!(content.contains(
+            "### internal!\n\n```rust\nmacro_rules! internal {\n    [$name:ident; $($arg:tt)*] => { ... };\n}"
+        ));
+    }
+
+    #[test]
+    fn test_complete_docs_visitor_proc_macros() {
+        let code = r#"
+            /// Parses SQL at compile time.
+            #[proc_macro]
+            pub fn sql(input: TokenStream) -> TokenStream { input }
+
+            /// Times the function.
+            #[proc_macro_attribute]
+            pub fn timed(attr: TokenStream, item: TokenStream) -> TokenStream { item }
+
+            /// Derives `Builder`.
+            #[proc_macro_derive(Builder, attributes(builder, skip))]
+            pub fn derive_builder(input: TokenStream) -> TokenStream { input }
+
+            #[proc_macro_derive(Plain)]
+            pub fn derive_plain(input: TokenStream) -> TokenStream { input }
+        "#;
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.visit_file(&file);
+        let kinds: Vec<_> = visitor
+            .items
+            .into_iter()
+            .map(|item| (item.kind, item.path))
+            .collect();
+
+        assert!(
+            content.contains("### sql!\n\n```rust\nsql!(...)\n```\n\nParses SQL at compile time.")
+        );
+        assert!(content.contains("### #[timed]\n\n```rust\n#[timed(...)]\n```\n"));
+        assert!(content.contains(
+            "### #[derive(Builder)]\n\n```rust\n#[derive(Builder)]\n{\n    \
+             // Attributes available to this derive:\n    #[builder]\n    #[skip]\n}\n```\n"
+        ));
+        assert!(content.contains("```rust\n#[derive(Plain)]\n```\n"));
+        // 実装の関数シグネチャは出力しない
+        assert!(!content.contains("TokenStream"));
+
+        assert_eq!(
+            kinds,
+            vec![
+                (ItemKind::ProcMacro, "sql!".to_string()),
+                (ItemKind::AttributeMacro, "timed".to_string()),
+                (ItemKind::DeriveMacro, "Builder".to_string()),
+                (ItemKind::DeriveMacro, "Plain".to_string()),
+            ]
+        );
+
+        let mut items = Vec::new();
+        TocVisitor::new(&mut items).visit_file(&file);
+        assert_eq!(
+            items,
+            vec!["sql!", "#[timed]", "#[derive(Builder)]", "#[derive(Plain)]"]
+        );
+    }
+
     #[test]
     fn test_format_function_signature_with_where_clause() {
         // Test function with where clause
         let sig: syn::Signature =
             syn::parse_str("fn with_where<T>(value: T) -> T where T: Clone").unwrap();
-        let result = format_function_signature(&sig, false, "    ");
+        let result = format_function_signature(&sig, "", "    ");
         assert!(result.contains("fn with_where<T>(value: T) -> T"));
         assert!(result.contains("where"));
         assert!(result.contains("T: Clone"));
@@ -1950,7 +4107,7 @@ mod tests {
         let sig: syn::Signature =
             syn::parse_str("fn complex_where<T, U>(t: T, u: U) -> (T, U) where T: Clone, U: Send")
                 .unwrap();
-        let result = format_function_signature(&sig, true, "");
+        let result = format_function_signature(&sig, "pub ", "");
         assert!(result.contains("pub fn complex_where<T, U>(t: T, u: U) -> (T, U)"));
         assert!(result.contains("where"));
         assert!(result.contains("T: Clone"));
@@ -1959,7 +4116,7 @@ mod tests {
         // Test lifetime and const generics
         let sig: syn::Signature =
             syn::parse_str("fn lifetime_generic<'a, T>(data: &'a T) -> &'a T where T: 'a").unwrap();
-        let result = format_function_signature(&sig, false, "  ");
+        let result = format_function_signature(&sig, "", "  ");
         assert!(result.contains("fn lifetime_generic<'a, T>(data: &'a T) -> &'a T"));
         assert!(result.contains("where"));
         assert!(result.contains("T: 'a"));
@@ -1971,11 +4128,11 @@ mod tests {
         let sig: syn::Signature =
             syn::parse_str("fn multiple_lifetimes<'a, 'b>(x: &'a str, y: &'b str) -> &'a str")
                 .unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert!(result.contains("fn multiple_lifetimes<'a, 'b>(x: &'a str, y: &'b str) -> &'a str"));
 
         // Test with pub
-        let result = format_function_signature(&sig, true, "");
+        let result = format_function_signature(&sig, "pub ", "");
         assert!(
             result.contains("pub fn multiple_lifetimes<'a, 'b>(x: &'a str, y: &'b str) -> &'a str")
         );
@@ -1985,7 +4142,7 @@ mod tests {
             "fn complex_lifetimes<'a, 'b, T>(x: &'a T, y: &'b T) -> &'a T where 'b: 'a, T: Clone",
         )
         .unwrap();
-        let result = format_function_signature(&sig, false, "    ");
+        let result = format_function_signature(&sig, "", "    ");
         assert!(result.contains("fn complex_lifetimes<'a, 'b, T>(x: &'a T, y: &'b T) -> &'a T"));
         assert!(result.contains("where"));
         assert!(result.contains("'b: 'a"));
@@ -1993,18 +4150,18 @@ mod tests {
 
         // Test with mutable references and multiple lifetimes
         let sig: syn::Signature = syn::parse_str("fn mut_multiple_lifetimes<'a, 'b>(&mut self, x: &'a mut String, y: &'b str) -> &'a String").unwrap();
-        let result = format_function_signature(&sig, true, "");
+        let result = format_function_signature(&sig, "pub ", "");
         assert!(result.contains("pub fn mut_multiple_lifetimes<'a, 'b>(&mut self, x: &'a mut String, y: &'b str) -> &'a String"));
 
         // Test with static lifetime
         let sig: syn::Signature =
             syn::parse_str("fn with_static<'a>(x: &'a str, y: &'static str) -> &'a str").unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert!(result.contains("fn with_static<'a>(x: &'a str, y: &'static str) -> &'a str"));
 
         // Test complex lifetime bounds in where clause
         let sig: syn::Signature = syn::parse_str("fn lifetime_bounds<'a, 'b, 'c, T>(data: &'a T) -> &'a T where 'b: 'a, 'c: 'b, T: 'a + 'b").unwrap();
-        let result = format_function_signature(&sig, false, "  ");
+        let result = format_function_signature(&sig, "", "  ");
         assert!(result.contains("fn lifetime_bounds<'a, 'b, 'c, T>(data: &'a T) -> &'a T"));
         assert!(result.contains("where"));
         assert!(result.contains("'b: 'a"));
@@ -2019,7 +4176,7 @@ mod tests {
             "fn lifetime_param_bounds<'a: 'b, 'b>(x: &'a str, y: &'b str) -> &'b str",
         )
         .unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert!(result.contains("fn lifetime_param_bounds"));
         assert!(result.contains("'a: 'b"));
         assert!(result.contains("'b"));
@@ -2030,7 +4187,7 @@ mod tests {
             "fn complex_lifetime_bounds<'a: 'b + 'c, 'b, 'c>(data: &'a str) -> &'a str",
         )
         .unwrap();
-        let result = format_function_signature(&sig, true, "");
+        let result = format_function_signature(&sig, "pub ", "");
         assert!(result.contains("pub fn complex_lifetime_bounds"));
         assert!(result.contains("'a: 'b + 'c"));
         assert!(result.contains("'b"));
@@ -2041,7 +4198,7 @@ mod tests {
             "fn mixed_bounds<'a: 'b, 'b, T>(x: &'a T, y: &'b T) -> &'a T where T: Clone + 'a",
         )
         .unwrap();
-        let result = format_function_signature(&sig, false, "    ");
+        let result = format_function_signature(&sig, "", "    ");
         assert!(result.contains("fn mixed_bounds"));
         assert!(result.contains("'a: 'b"));
         assert!(result.contains("'b"));
@@ -2052,7 +4209,7 @@ mod tests {
         // Test static lifetime bounds
         let sig: syn::Signature =
             syn::parse_str("fn static_bounds<'a: 'static>(data: &'a str) -> &'a str").unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert!(result.contains("'a: 'static"));
     }
 
@@ -2061,7 +4218,7 @@ mod tests {
         // Test type parameter bounds like <A: B, B: C, C>
         let sig: syn::Signature =
             syn::parse_str("fn type_bounds<A: B, B: C, C>(a: A, b: B, c: C) -> A").unwrap();
-        let result = format_function_signature(&sig, false, "");
+        let result = format_function_signature(&sig, "", "");
         assert!(result.contains("fn type_bounds"));
         assert!(result.contains("A: B"));
         assert!(result.contains("B: C"));
@@ -2072,7 +4229,7 @@ mod tests {
             "fn complex_type_bounds<T: Clone + Send, U: T + Debug>(t: T, u: U) -> T",
         )
         .unwrap();
-        let result = format_function_signature(&sig, true, "");
+        let result = format_function_signature(&sig, "pub ", "");
         assert!(result.contains("pub fn complex_type_bounds"));
         assert!(result.contains("T: Clone + Send"));
         assert!(result.contains("U: T + Debug"));
@@ -2166,6 +4323,42 @@ mod tests {
         assert_eq!(result, "");
     }
 
+    #[test]
+    fn test_format_generics() {
+        let generics: syn::Generics = syn::parse_str(
+            "<'a, 'b: 'a, T: Clone + ?Sized + 'a, U: Into<String> = (), const N: usize = 4>",
+        )
+        .unwrap();
+        assert_eq!(
+            format_generics(&generics),
+            "<'a, 'b: 'a, T: Clone + ?Sized + 'a, U: Into<String> = (), const N: usize = 4>"
+        );
+        assert_eq!(format_generics(&syn::Generics::default()), "");
+    }
+
+    #[test]
+    fn test_complete_docs_visitor_where_clauses() {
+        let code = r#"
+            pub struct Named<T> where T: Clone { pub value: T }
+            pub struct Tuple<T>(pub T) where T: Copy;
+            pub enum Either<L, R = L> where for<'a> &'a L: Into<R> { Left(L), Right(R) }
+            pub trait Store<K: Ord>: Send where K: Clone {}
+        "#;
+        let file: syn::File = syn::parse_str(code).unwrap();
+        let mut content = String::new();
+        let mut visitor = CompleteDocsVisitor::new(&mut content);
+        visitor.visit_file(&file);
+
+        assert!(
+            content.contains("pub struct Named<T>\nwhere\n    T: Clone\n{\n    pub value: T,\n}")
+        );
+        assert!(content.contains("pub struct Tuple<T>(pub T)\nwhere\n    T: Copy;"));
+        assert!(content.contains(
+            "pub enum Either<L, R = L>\nwhere\n    for<'a> &'a L: Into<R>\n{\n    Left(L),"
+        ));
+        assert!(content.contains("pub trait Store<K: Ord>: Send\nwhere\n    K: Clone\n{\n}"));
+    }
+
     #[test]
     fn test_format_generic_params_simple_complex() {
         // Test complex mixed parameters
@@ -0,0 +1,4 @@
+/// Never documented.
+pub fn unfinished(a: i32 -> i32 {
+    a
+}
@@ -0,0 +1,2 @@
+/// Still documented.
+pub fn healthy() {}
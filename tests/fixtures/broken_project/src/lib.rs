@@ -0,0 +1,4 @@
+//! Fixture for unparseable source files.
+
+pub mod broken;
+pub mod good;
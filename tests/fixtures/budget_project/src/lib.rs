@@ -0,0 +1,8 @@
+//! Storage engine entry points.
+
+pub mod storage;
+
+/// Opens a database at the given path
+pub fn open(path: &str) -> storage::Database {
+    storage::Database { path: path.to_string() }
+}
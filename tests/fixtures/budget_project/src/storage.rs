@@ -0,0 +1,10 @@
+//! On-disk storage.
+
+pub mod internals;
+
+/// A handle to an open database
+pub struct Database {
+    pub path: String,
+}
+
+pub fn compact_all_segments_without_any_documentation(db: &Database) {}
@@ -0,0 +1,7 @@
+pub fn rebuild_segment_index_with_a_very_long_name(level: usize) -> usize {
+    level
+}
+
+pub fn verify_segment_checksums_with_a_very_long_name(level: usize) -> bool {
+    level > 0
+}
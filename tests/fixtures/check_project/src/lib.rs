@@ -0,0 +1,6 @@
+//! Fixture for `--check`.
+
+/// Adds two numbers.
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
@@ -0,0 +1,6 @@
+//! Networking utilities.
+
+pub mod net;
+
+/// Library version string
+pub const VERSION: &str = "0.1.0";
@@ -0,0 +1,11 @@
+//! Connections and addresses.
+
+pub mod addr;
+
+/// An open connection
+pub struct Connection;
+
+/// Connects to a remote host
+pub fn connect(host: &str) -> Connection {
+    Connection
+}
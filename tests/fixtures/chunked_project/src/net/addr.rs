@@ -0,0 +1,4 @@
+/// Parses a socket address
+pub fn parse(input: &str) -> Option<(String, u16)> {
+    None
+}
@@ -122,4 +122,20 @@ macro_rules! complex_macro {
     ($name:ident, $value:expr) => {
         let $name = ComplexStruct::new($value, "metadata".to_string());
     };
-}
\ No newline at end of file
+}
+/// A point formatted in several ways
+pub struct Point(pub i32);
+
+impl std::fmt::Display for Point {
+    /// Formats the point as decimal
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::fmt::Binary for Point {
+    /// Formats the point as binary
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:b}", self.0)
+    }
+}
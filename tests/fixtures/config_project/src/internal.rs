@@ -0,0 +1,4 @@
+//! Internal details.
+
+/// Should not be documented.
+pub fn secret() {}
@@ -0,0 +1,13 @@
+//! Fixture for `[package.metadata.llms-txt]`.
+
+pub mod internal;
+
+/// Runs the project.
+pub fn run() {}
+
+/// Support for generated code.
+#[doc(hidden)]
+pub fn __private_support() {}
+
+/// Shared helper.
+pub(crate) fn helper() {}
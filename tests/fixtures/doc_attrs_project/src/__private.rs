@@ -0,0 +1 @@
+pub fn private_support() {}
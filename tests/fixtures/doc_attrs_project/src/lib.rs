@@ -0,0 +1,26 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+#[doc(hidden)]
+pub mod __private;
+
+#[doc = include_str!("../docs/engine.md")]
+pub struct Engine;
+
+impl Engine {
+    pub fn new() -> Self {
+        Engine
+    }
+
+    #[doc(hidden)]
+    pub fn __debug_state(&self) {}
+}
+
+/// Serializes the engine state
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub fn to_json(_engine: &Engine) -> String {
+    String::new()
+}
+
+#[doc(hidden)]
+pub fn __macro_support() {}
@@ -0,0 +1,58 @@
+//! Fixture for doc examples.
+//!
+//! ```
+//! # fn main() {
+//! let total = doctest_project::add(1, 2);
+//! # }
+//! ```
+
+/// Adds two numbers.
+///
+/// # Examples
+///
+/// ```
+/// # use doctest_project::add;
+/// assert_eq!(add(2, 2), 4);
+/// ```
+///
+/// ```toml
+/// [dependencies]
+/// doctest_project = "0.1"
+/// ```
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+/// Starts a server.
+///
+/// ```no_run
+/// doctest_project::serve();
+/// ```
+pub fn serve() {}
+
+/// Always panics.
+///
+/// ```should_panic
+/// doctest_project::fail();
+/// ```
+///
+/// ```ignore
+/// not_compiled();
+/// ```
+pub fn fail() {
+    panic!("failed");
+}
+
+/// Has no examples.
+pub fn plain() {}
+
+/// Prints the even numbers.
+///
+/// ```
+/// for n in 0..10 {
+///     if n % 2 == 0 {
+///         println!("{}", n);
+///     }
+/// }
+/// ```
+pub fn evens() {}
@@ -0,0 +1,5 @@
+//! Declared with a custom path.
+
+fn main() {
+    let _ = examples_project::greet("custom");
+}
@@ -0,0 +1,9 @@
+//! Prints a greeting.
+//!
+//! # Usage
+//!
+//! Run it without arguments.
+
+fn main() {
+    println!("{}", examples_project::greet("world"));
+}
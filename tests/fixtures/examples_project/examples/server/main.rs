@@ -0,0 +1,34 @@
+//! A long-running server.
+
+fn main() {
+    let _line_1 = 1;
+    let _line_2 = 2;
+    let _line_3 = 3;
+    let _line_4 = 4;
+    let _line_5 = 5;
+    let _line_6 = 6;
+    let _line_7 = 7;
+    let _line_8 = 8;
+    let _line_9 = 9;
+    let _line_10 = 10;
+    let _line_11 = 11;
+    let _line_12 = 12;
+    let _line_13 = 13;
+    let _line_14 = 14;
+    let _line_15 = 15;
+    let _line_16 = 16;
+    let _line_17 = 17;
+    let _line_18 = 18;
+    let _line_19 = 19;
+    let _line_20 = 20;
+    let _line_21 = 21;
+    let _line_22 = 22;
+    let _line_23 = 23;
+    let _line_24 = 24;
+    let _line_25 = 25;
+    let _line_26 = 26;
+    let _line_27 = 27;
+    let _line_28 = 28;
+    let _line_29 = 29;
+    let _line_30 = 30;
+}
@@ -0,0 +1,6 @@
+//! Fixture for example programs.
+
+/// Greets someone.
+pub fn greet(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
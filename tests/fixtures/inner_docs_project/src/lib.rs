@@ -0,0 +1,20 @@
+//! Tools for parsing and rendering
+//! configuration files.
+//!
+//! Start with [`parse`] to read a document.
+
+pub mod render;
+
+/// Inline helpers for formatting values
+pub mod format {
+    //! Formatting is locale independent.
+
+    pub fn pad(value: &str) -> String {
+        value.to_string()
+    }
+}
+
+/// Parses a document
+pub fn parse(input: &str) -> usize {
+    input.len()
+}
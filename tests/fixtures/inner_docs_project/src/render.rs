@@ -0,0 +1,3 @@
+//! Rendering of parsed documents.
+
+pub fn render() {}
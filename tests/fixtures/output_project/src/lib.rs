@@ -0,0 +1,6 @@
+//! Fixture for `--output-dir` and `--stdout`.
+
+/// Greets someone.
+pub fn greet(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
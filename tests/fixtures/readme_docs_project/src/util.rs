@@ -0,0 +1,6 @@
+#![doc = include_str!("../docs/util.md")]
+
+/// Trims the input.
+pub fn trim(input: &str) -> &str {
+    input.trim()
+}
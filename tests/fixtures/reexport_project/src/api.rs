@@ -0,0 +1,6 @@
+//! The public API module
+
+pub use crate::internal::engine::Engine as ApiEngine;
+
+/// A public function in a public module
+pub fn api_function() {}
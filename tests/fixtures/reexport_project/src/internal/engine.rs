@@ -0,0 +1,4 @@
+/// The engine, defined in a private module
+pub struct Engine {
+    pub power: u32,
+}
@@ -0,0 +1,4 @@
+/// A helper re-exported through a glob
+pub fn helper() {}
+
+pub(crate) fn crate_only_helper() {}
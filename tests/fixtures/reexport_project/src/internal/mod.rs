@@ -0,0 +1,5 @@
+pub mod engine;
+pub mod helpers;
+
+/// Public, but never re-exported
+pub struct Unreachable;
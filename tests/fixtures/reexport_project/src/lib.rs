@@ -0,0 +1,7 @@
+//! A library that exposes items defined in private modules
+
+mod internal;
+pub mod api;
+
+pub use internal::engine::Engine;
+pub use internal::helpers::*;
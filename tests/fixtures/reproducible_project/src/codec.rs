@@ -0,0 +1,6 @@
+//! Encoding helpers.
+
+/// Encodes a value.
+pub fn encode(value: &[u8]) -> Vec<u8> {
+    value.to_vec()
+}
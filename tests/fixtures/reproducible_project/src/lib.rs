@@ -0,0 +1,8 @@
+//! Fixture for reproducible output.
+
+pub mod codec;
+
+/// Adds two numbers.
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
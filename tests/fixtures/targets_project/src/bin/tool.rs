@@ -0,0 +1,6 @@
+//! A helper tool.
+
+/// Runs the tool.
+pub fn run() {}
+
+fn main() {}
@@ -0,0 +1,4 @@
+//! Shared library code.
+
+/// Runs the library.
+pub fn run() {}
@@ -0,0 +1,6 @@
+//! The main binary.
+
+/// Runs the main binary.
+pub fn run() {}
+
+fn main() {}
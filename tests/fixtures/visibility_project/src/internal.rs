@@ -0,0 +1,4 @@
+/// Prepares the crate before running
+pub(crate) fn prepare() {}
+
+pub(super) fn parent_only() {}
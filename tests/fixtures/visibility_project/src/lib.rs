@@ -0,0 +1,14 @@
+mod internal;
+
+/// Public entry point
+pub fn run() {
+    internal::prepare();
+}
+
+/// Shared state used across the crate
+pub(crate) struct State {
+    pub(crate) ready: bool,
+    counter: u32,
+}
+
+fn private_helper() {}
@@ -0,0 +1,12 @@
+//! The alpha crate
+
+/// A struct from the alpha crate
+pub struct AlphaStruct {
+    /// The alpha value
+    pub value: u32,
+}
+
+/// A function from the alpha crate
+pub fn alpha_function() -> AlphaStruct {
+    AlphaStruct { value: 1 }
+}
@@ -0,0 +1,6 @@
+//! The beta crate
+
+/// A function from the beta crate
+pub fn beta_function(input: &str) -> String {
+    input.to_uppercase()
+}
@@ -0,0 +1,2 @@
+/// This crate is excluded from the workspace
+pub fn excluded_function() {}
@@ -75,12 +75,13 @@ fn test_basic_llms_txt_generation() {
     );
 
     // Test for the 6 new public item types
+    // 再エクスポートは束縛された名前で載せる
     assert!(
-        content.contains("pub use std::collections::HashMap"),
+        content.contains("- pub use HashMap\n"),
         "HashMap re-export not found in TOC"
     );
     assert!(
-        content.contains("pub use std::vec::Vec as SimpleVec"),
+        content.contains("- pub use SimpleVec\n"),
         "SimpleVec alias not found in TOC"
     );
     assert!(
@@ -167,6 +168,38 @@ fn test_llms_full_txt_generation() {
         content.contains("ComplexEnum"),
         "ComplexEnum not found in full version"
     );
+    assert!(
+        content.contains("##### ComplexStruct::new\n\n```rust\npub fn new(data: T, metadata: U) -> Self\n```\n\nCreates a new ComplexStruct"),
+        "Method of ComplexStruct not documented under the type"
+    );
+    assert!(
+        content.contains(
+            "#### Trait Implementations\n\n##### impl ComplexTrait<'a, T> for ComplexStruct<T, U>"
+        ),
+        "Trait impl not grouped under ComplexStruct"
+    );
+    assert!(
+        content.contains(
+            "- `impl<T, U> Clone for ComplexStruct<T, U> where T: Clone + std::fmt::Display, U: std::fmt::Debug` (derived)"
+        ),
+        "Derived impl not listed under ComplexStruct"
+    );
+    assert!(
+        !content.contains("- impl "),
+        "Impl blocks should not be listed in the table of contents"
+    );
+    assert!(
+        content.contains(
+            "macro_rules! complex_macro {\n    ($name:ident, $value:expr) => { ... };\n}"
+        ),
+        "Macro matcher not rendered"
+    );
+    assert!(
+        content.contains(
+            "    /// Struct variant\n    Struct {\n        /// The value field\n        value: T,"
+        ),
+        "Variant and field docs not rendered"
+    );
 }
 
 #[test]
@@ -234,3 +267,879 @@ fn test_current_directory_default() {
         "llms-full.txt was not generated in current directory"
     );
 }
+
+#[test]
+fn test_workspace_generation() {
+    let project_path = Path::new("tests/fixtures/workspace_project");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // ルートのインデックスを確認
+    let index = fs::read_to_string(project_path.join("llms.txt")).expect("Failed to read llms.txt");
+    assert!(
+        index.contains("## Workspace Members"),
+        "Workspace members section not found"
+    );
+    assert!(
+        index.contains("[llms.txt](crates/alpha/llms.txt)"),
+        "alpha llms.txt link not found"
+    );
+    assert!(
+        index.contains("[llms-full.txt](crates/beta/llms-full.txt)"),
+        "beta llms-full.txt link not found"
+    );
+    assert!(
+        !index.contains("crates/excluded/llms.txt"),
+        "Excluded member should not be listed"
+    );
+    assert!(
+        !project_path.join("llms-full.txt").exists(),
+        "Virtual workspace root should not get llms-full.txt"
+    );
+
+    // 各メンバーのファイルを確認
+    let alpha_full = fs::read_to_string(project_path.join("crates/alpha/llms-full.txt"))
+        .expect("Failed to read alpha llms-full.txt");
+    assert!(
+        alpha_full.contains("AlphaStruct"),
+        "AlphaStruct not found in alpha llms-full.txt"
+    );
+    let alpha = fs::read_to_string(project_path.join("crates/alpha/llms.txt"))
+        .expect("Failed to read alpha llms.txt");
+    assert!(
+        alpha.contains("**Version:** 0.3.0"),
+        "Inherited version not found in alpha llms.txt"
+    );
+    assert!(
+        project_path.join("crates/beta/llms.txt").exists(),
+        "beta llms.txt was not generated"
+    );
+    assert!(
+        !project_path.join("crates/excluded/llms.txt").exists(),
+        "Excluded member should not be generated"
+    );
+}
+
+#[test]
+fn test_reexported_public_api() {
+    let project_path = Path::new("tests/fixtures/reexport_project");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+
+    // 非公開モジュールで定義された再エクスポートは公開パスで出力される
+    assert!(
+        content.contains("### Engine\n"),
+        "Engine not documented under its public path"
+    );
+    assert!(
+        content.contains("*Re-exported from `internal::engine::Engine`*"),
+        "Re-export origin of Engine not found"
+    );
+    assert!(
+        content.contains("*Also available as `api::ApiEngine`*"),
+        "Alias of Engine not found"
+    );
+    assert!(
+        content.contains("### helper\n"),
+        "Glob re-exported helper not found"
+    );
+    assert!(
+        content.contains("### api::api_function"),
+        "api::api_function not found"
+    );
+    assert!(
+        !content.contains("pub use internal::engine::Engine;"),
+        "Local re-export should be documented at the item instead"
+    );
+
+    // 到達不可能な pub アイテムは出力されない
+    assert!(
+        !content.contains("Unreachable"),
+        "Unreachable item should not be documented"
+    );
+    assert!(
+        !content.contains("crate_only_helper"),
+        "pub(crate) item should not be documented"
+    );
+
+    let toc = fs::read_to_string(project_path.join("llms.txt")).expect("Failed to read llms.txt");
+    assert!(
+        toc.contains("- pub struct Engine"),
+        "Engine not listed in TOC"
+    );
+    assert!(
+        !toc.contains("Unreachable"),
+        "Unreachable item should not be listed in TOC"
+    );
+}
+
+#[test]
+fn test_visibility_levels() {
+    let project_path = Path::new("tests/fixtures/visibility_project");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .arg("--visibility")
+        .arg("all")
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+
+    // クレート内で可視なアイテムが可視性付きで出力される
+    assert!(
+        content.contains("pub(crate) struct State"),
+        "pub(crate) struct not documented with its visibility"
+    );
+    assert!(
+        content.contains("    pub(crate) ready: bool,"),
+        "pub(crate) field not documented"
+    );
+    assert!(
+        content.contains("### internal::prepare"),
+        "Item in private module not documented under its local path"
+    );
+    assert!(
+        content.contains("pub(super) fn parent_only()"),
+        "pub(super) function not documented"
+    );
+
+    // 非公開アイテムも出力される
+    assert!(
+        content.contains("fn private_helper()"),
+        "Private function not documented"
+    );
+    assert!(
+        content.contains("    counter: u32,"),
+        "Private field not documented"
+    );
+
+    let toc = fs::read_to_string(project_path.join("llms.txt")).expect("Failed to read llms.txt");
+    assert!(
+        toc.contains("- pub(crate) fn internal::prepare"),
+        "pub(crate) function not listed in TOC"
+    );
+}
+
+#[test]
+fn test_doc_attributes() {
+    let project_path = Path::new("tests/fixtures/doc_attrs_project");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+
+    // include_str! のドキュメントはソースファイルからの相対パスで読み込まれる
+    assert!(
+        content.contains("The engine drives every job."),
+        "include_str! doc not inlined"
+    );
+    assert!(
+        content.contains("#### Usage"),
+        "Heading in included doc not adjusted"
+    );
+
+    // doc(cfg) は利用条件のバッジとして出力される
+    assert!(
+        content.contains("*Available on crate feature `serde` only.*"),
+        "doc(cfg) badge not found"
+    );
+
+    // doc(hidden) のアイテムとモジュールは出力されない
+    assert!(
+        !content.contains("__macro_support"),
+        "Hidden function should not be documented"
+    );
+    assert!(
+        !content.contains("__debug_state"),
+        "Hidden method should not be documented"
+    );
+    assert!(
+        !content.contains("private_support"),
+        "Items in hidden module should not be documented"
+    );
+
+    let toc = fs::read_to_string(project_path.join("llms.txt")).expect("Failed to read llms.txt");
+    assert!(
+        !toc.contains("__private"),
+        "Hidden module should not be listed in TOC"
+    );
+}
+
+#[test]
+fn test_module_inner_docs() {
+    let project_path = Path::new("tests/fixtures/inner_docs_project");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // description がない場合はクレートルートの `//!` が概要になる
+    let toc = fs::read_to_string(project_path.join("llms.txt")).expect("Failed to read llms.txt");
+    assert!(
+        toc.contains("> Tools for parsing and rendering configuration files.\n"),
+        "Crate docs not used as summary"
+    );
+    assert!(
+        toc.contains("### src/render.rs\n\nRendering of parsed documents.\n"),
+        "Module summary not found in TOC"
+    );
+    assert!(
+        toc.contains("- pub mod format: Inline helpers for formatting values"),
+        "Inline module summary not found in TOC"
+    );
+
+    let content = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+    assert!(
+        content.contains(
+            "## crate\n\n*Source: `src/lib.rs`*\n\nTools for parsing and rendering\nconfiguration files.\n"
+        ),
+        "Crate overview not rendered before items"
+    );
+    assert!(
+        content.contains(
+            "## mod format\n\n*Source: `src/lib.rs`*\n\nInline helpers for formatting values\nFormatting is locale independent.\n"
+        ),
+        "Inline module overview not found"
+    );
+    assert!(
+        content.contains("### format::pad"),
+        "Item in inline module not documented under its path"
+    );
+    assert!(
+        content.contains(
+            "## mod render\n\n*Source: `src/render.rs`*\n\nRendering of parsed documents.\n"
+        ),
+        "File module overview not found"
+    );
+}
+
+#[test]
+fn test_json_output() {
+    let project_path = Path::new("tests/fixtures/complex_project");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .arg("--format")
+        .arg("json")
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content =
+        fs::read_to_string(project_path.join("llms.json")).expect("Failed to read llms.json");
+    let model: serde_json::Value = serde_json::from_str(&content).expect("Invalid JSON");
+
+    assert_eq!(model["name"], "complex_project");
+    assert_eq!(model["version"], "0.2.0");
+
+    let root = &model["modules"][0];
+    assert_eq!(root["path"], "crate");
+    assert_eq!(root["source_file"], "src/lib.rs");
+
+    let items = root["items"].as_array().expect("items should be an array");
+    let item = |path: &str| {
+        items
+            .iter()
+            .find(|item| item["path"] == path)
+            .unwrap_or_else(|| panic!("{} not found in llms.json", path))
+    };
+
+    let complex_struct = item("ComplexStruct");
+    assert_eq!(complex_struct["kind"], "struct");
+    assert_eq!(complex_struct["docs"], "A complex struct with generics");
+    assert_eq!(
+        complex_struct["derives"],
+        serde_json::json!(["Debug", "Clone", "Serialize", "Deserialize"])
+    );
+    assert_eq!(complex_struct["span"]["file"], "src/lib.rs");
+    assert!(complex_struct["span"]["start_line"].as_u64().unwrap() > 0);
+
+    let new = item("ComplexStruct::new");
+    assert_eq!(new["kind"], "method");
+    assert_eq!(new["signature"], "pub fn new(data: T, metadata: U) -> Self");
+
+    // トレイト実装のメソッドはトレイトごとに異なるパスで記録する
+    let display = item("<Point as std::fmt::Display>::fmt");
+    assert_eq!(display["kind"], "method");
+    assert_eq!(display["docs"], "Formats the point as decimal");
+    let binary = item("<Point as std::fmt::Binary>::fmt");
+    assert_eq!(binary["docs"], "Formats the point as binary");
+    assert!(!items.iter().any(|item| item["path"] == "Point::fmt"));
+}
+
+#[test]
+fn test_max_tokens_budget() {
+    let project_path = Path::new("tests/fixtures/budget_project");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .arg("--max-tokens")
+        .arg("300")
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // 削除したセクションが報告される
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("llms-full.txt: trimmed to"));
+    assert!(stderr.contains("  - storage::internals::rebuild_segment_index_with_a_very_long_name"));
+
+    let content = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+
+    // クレートの docs とトップレベルのアイテムは残る
+    assert!(content.contains("# budget_project - Complete API Documentation"));
+    assert!(content.contains("Storage engine entry points."));
+    assert!(content.contains("### open\n"));
+
+    // 深いモジュールと README から削除される
+    assert!(!content.contains("rebuild_segment_index_with_a_very_long_name"));
+    assert!(!content.contains("## mod storage::internals"));
+    assert!(!content.contains("## README.md"));
+    assert!(content.contains("> **Note:** 9 sections were omitted to fit within 300 tokens.\n"));
+}
+
+#[test]
+fn test_chunked_output() {
+    let project_path = Path::new("tests/fixtures/chunked_project");
+    let chunk_dir = project_path.join("llms-full");
+
+    for mode in ["size", "module"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+            .arg("--path")
+            .arg(project_path)
+            .arg("--chunks")
+            .arg(mode)
+            .arg("--chunk-tokens")
+            .arg("120")
+            .output()
+            .expect("Failed to execute cargo-llms-txt");
+
+        assert!(
+            output.status.success(),
+            "Command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    // 後から実行したモジュールごとの出力だけが残る
+    let mut files: Vec<String> = fs::read_dir(&chunk_dir)
+        .expect("llms-full/ was not generated")
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec!["crate.md", "manifest.json", "net.addr.md", "net.md"]
+    );
+
+    let manifest: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(chunk_dir.join("manifest.json")).expect("Failed to read manifest"),
+    )
+    .expect("Invalid manifest JSON");
+    assert_eq!(manifest["name"], "chunked_project");
+
+    let net = &manifest["chunks"][1];
+    assert_eq!(net["id"], "net");
+    assert_eq!(net["file"], "net.md");
+    assert_eq!(net["modules"], serde_json::json!(["net"]));
+    assert_eq!(
+        net["items"],
+        serde_json::json!(["net::Connection", "net::connect"])
+    );
+    assert!(net["tokens"].as_u64().unwrap() > 0);
+
+    let content = fs::read_to_string(chunk_dir.join("net.md")).expect("Failed to read net.md");
+    assert!(content.starts_with("# chunked_project\n\n## mod net\n\n*Source: `src/net.rs`*\n\n"));
+    assert!(content.contains("### net::connect"));
+    assert!(!content.contains("net::addr::parse"));
+
+    // llms.txt はチャンクの manifest にリンクする
+    let llms_txt =
+        fs::read_to_string(project_path.join("llms.txt")).expect("Failed to read llms.txt");
+    assert!(llms_txt.contains("- [Complete API Documentation](llms-full/manifest.json)"));
+    assert!(!project_path.join("llms-full.txt").exists());
+}
+
+#[test]
+fn test_check_mode() {
+    let project_path = Path::new("tests/fixtures/check_project");
+    let run = |check: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"));
+        command.arg("--path").arg(project_path);
+        if check {
+            command.arg("--check");
+        }
+        command.output().expect("Failed to execute cargo-llms-txt")
+    };
+
+    let output = run(false);
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // 生成直後は Generated: の行が変わっても最新と判定される
+    let output = run(true);
+    assert!(
+        output.status.success(),
+        "Check failed: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    // ディスク上のファイルが古ければ差分を表示して失敗し、ファイルは書き換えない
+    let llms_path = project_path.join("llms.txt");
+    let edited = fs::read_to_string(&llms_path)
+        .expect("Failed to read llms.txt")
+        .replace("A project for testing --check", "An outdated description");
+    fs::write(&llms_path, &edited).expect("Failed to write llms.txt");
+
+    let output = run(true);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("llms.txt"), "{}", stdout);
+    assert!(stdout.contains("-> An outdated description"), "{}", stdout);
+    assert!(
+        stdout.contains("+> A project for testing --check"),
+        "{}",
+        stdout
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 generated file(s) are out of date"));
+    assert_eq!(fs::read_to_string(&llms_path).unwrap(), edited);
+}
+
+#[test]
+fn test_reproducible_output() {
+    let project_path = Path::new("tests/fixtures/reproducible_project");
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+            .arg("--path")
+            .arg(project_path)
+            .args(args)
+            .env("SOURCE_DATE_EPOCH", "1700000000")
+            .output()
+            .expect("Failed to execute cargo-llms-txt");
+        assert!(
+            output.status.success(),
+            "Command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        ["llms.txt", "llms-full.txt"].map(|name| {
+            fs::read(project_path.join(name)).unwrap_or_else(|_| panic!("Failed to read {}", name))
+        })
+    };
+
+    // 同じ入力からは実行するたびにバイト単位で同じ出力になる
+    let first = run(&[]);
+    let second = run(&[]);
+    assert_eq!(first, second);
+
+    let llms_txt = String::from_utf8(first[0].clone()).unwrap();
+    assert!(llms_txt.contains("Generated: 2023-11-14 22:13:20 UTC"));
+    assert!(llms_txt.contains(
+        "**Dependencies:**\n- anyhow (1.0)\n- log (0.4)\n- regex (1.10)\n- serde (1.0) [features: derive, rc]\n- tokio (1.0) [features: macros, rt]\n"
+    ));
+    assert!(llms_txt.contains(
+        "**Features:**\n- alloc: []\n- default: [std]\n- std: []\n- tracing: []\n- unstable: [alloc]\n"
+    ));
+
+    // --timestamp は SOURCE_DATE_EPOCH より優先し、--no-timestamp は行ごと省く
+    let pinned = run(&["--timestamp", "0"]);
+    assert!(String::from_utf8_lossy(&pinned[0]).contains("Generated: 1970-01-01 00:00:00 UTC"));
+    let omitted = run(&["--no-timestamp"]);
+    for content in &omitted {
+        assert!(!String::from_utf8_lossy(content).contains("Generated:"));
+    }
+}
+
+#[test]
+fn test_config_from_metadata() {
+    let project_path = Path::new("tests/fixtures/config_project");
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+            .arg("--path")
+            .arg(project_path)
+            .args(args)
+            .output()
+            .expect("Failed to execute cargo-llms-txt");
+        assert!(
+            output.status.success(),
+            "Command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+
+    run(&[]);
+
+    // 出力先は [package.metadata.llms-txt.output] に従い、リンクはそこからの相対パスになる
+    assert!(!project_path.join("llms.txt").exists());
+    let llms_txt = fs::read_to_string(project_path.join("docs/llms.txt"))
+        .expect("Failed to read docs/llms.txt");
+    assert!(llms_txt.contains("- [Complete API Documentation](../llms-full.txt)"));
+    assert!(llms_txt.contains("- [README](../README.md)"));
+    assert!(llms_txt.contains("- [Guide](guide.md): How to use config_project"));
+    assert!(!llms_txt.contains("## Cargo.toml"));
+    assert!(!llms_txt.contains("src/internal.rs"));
+
+    // visibility は llms-txt.toml から、追加のドキュメントは llms-full.txt に含める
+    let full = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+    assert!(full.contains("pub(crate) fn helper()"));
+    assert!(full.contains("pub fn __private_support()"));
+    assert!(full.contains("## Guide\n\n### Getting started\n\nCall `run` to start."));
+    assert!(!full.contains("secret"));
+
+    // コマンドラインの指定は設定より優先される
+    run(&["--visibility", "public"]);
+    let full = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+    assert!(!full.contains("fn helper"));
+    assert!(full.contains("pub fn run()"));
+
+    // --no-include-hidden で設定の include-hidden = true を打ち消せる
+    run(&["--no-include-hidden"]);
+    let full = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+    assert!(!full.contains("__private_support"));
+    assert!(full.contains("pub(crate) fn helper()"));
+
+    // 後に指定したフラグが優先される
+    run(&["--no-include-hidden", "--include-hidden"]);
+    let full = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+    assert!(full.contains("pub fn __private_support()"));
+}
+
+#[test]
+fn test_output_options() {
+    let project_path = Path::new("tests/fixtures/output_project");
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+            .arg("--path")
+            .arg(project_path)
+            .args(args)
+            .output()
+            .expect("Failed to execute cargo-llms-txt");
+        assert!(
+            output.status.success(),
+            "Command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // --stdout は生成した内容だけを出力し、ファイルは書き込まない
+    let stdout = run(&["--stdout", "--only", "llms"]);
+    assert!(stdout.starts_with("# output_project\n"));
+    assert!(stdout.contains("- [README](README.md)"));
+    assert!(!project_path.join("llms.txt").exists());
+    assert!(!project_path.join("llms-full.txt").exists());
+
+    // --output-dir の下にファイル名を変えて出力し、リンクはそこからの相対パスになる
+    let output_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("output_project");
+    let _ = fs::remove_dir_all(&output_dir);
+    let output_arg = output_dir.to_str().unwrap();
+    run(&["--output-dir", output_arg, "--llms-file", "llms-small.txt"]);
+    let llms_txt = fs::read_to_string(output_dir.join("llms-small.txt"))
+        .expect("Failed to read llms-small.txt");
+    assert!(llms_txt.contains("- [Complete API Documentation](llms-full.txt)"));
+    assert!(!llms_txt.contains("- [README](README.md)"));
+    assert!(llms_txt.contains("tests/fixtures/output_project/README.md)"));
+    assert!(output_dir.join("llms-full.txt").exists());
+    assert!(!project_path.join("llms.txt").exists());
+
+    // --only full は llms-full.txt だけを生成する
+    fs::remove_dir_all(&output_dir).unwrap();
+    run(&["--output-dir", output_arg, "--only", "full"]);
+    assert!(output_dir.join("llms-full.txt").exists());
+    assert!(!output_dir.join("llms.txt").exists());
+}
+
+#[test]
+fn test_skipped_files() {
+    let project_path = Path::new("tests/fixtures/broken_project");
+    let run = |strict: bool| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"));
+        command.arg("--path").arg(project_path);
+        if strict {
+            command.arg("--strict");
+        }
+        command.output().expect("Failed to execute cargo-llms-txt")
+    };
+
+    // 構文エラーのあるファイルは警告を出して読み飛ばし、残りは出力する
+    let output = run(false);
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // 警告は生成するファイルの数によらず 1 度だけ出す
+    assert_eq!(
+        stderr.matches("warning: skipping src/broken.rs:2:").count(),
+        1,
+        "{}",
+        stderr
+    );
+
+    for file in ["llms.txt", "llms-full.txt"] {
+        let content = fs::read_to_string(project_path.join(file)).expect("Failed to read output");
+        assert!(content.contains("## Skipped Files\n\n"));
+        assert!(content.contains("- `src/broken.rs:2:"));
+        assert!(!content.contains("unfinished"));
+    }
+    let full = fs::read_to_string(project_path.join("llms-full.txt")).unwrap();
+    assert!(full.contains("pub fn healthy()"));
+
+    // --strict では最初の構文エラーで失敗する
+    let output = run(true);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to parse src/broken.rs:2:"));
+}
+
+#[test]
+fn test_examples() {
+    let project_path = Path::new("tests/fixtures/examples_project");
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .arg("--example-max-lines")
+        .arg("10")
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // llms.txt にはサンプルの一覧のみ
+    let llms = fs::read_to_string(project_path.join("llms.txt")).expect("Failed to read llms.txt");
+    assert!(llms.contains(
+        "## Examples\n\n\
+         - [custom](demos/custom.rs): Declared with a custom path.\n\
+         - [basic](examples/basic.rs): Prints a greeting.\n\
+         - [server](examples/server/main.rs): A long-running server.\n"
+    ));
+    assert!(!llms.contains("fn main()"));
+
+    // llms-full.txt には docs とコードを含める
+    let full = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+    assert!(full.contains(
+        "### basic\n\n*Source: `examples/basic.rs`* (run with `cargo run --example basic`)\n\n"
+    ));
+    assert!(full.contains("#### Usage\n\nRun it without arguments."));
+    assert!(full.contains("println!(\"{}\", examples_project::greet(\"world\"));"));
+
+    // 上限を超えるサンプルは切り詰める
+    assert!(full.contains(
+        "    let _line_9 = 9;\n```\n\n*22 more lines omitted; see `examples/server/main.rs`.*"
+    ));
+    assert!(!full.contains("_line_10 "));
+    // docs はコードの前に出力済みなのでリストには含めない
+    assert!(!full.contains("```rust\n//!"));
+}
+
+#[test]
+fn test_doc_examples() {
+    let project_path = Path::new("tests/fixtures/doctest_project");
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .arg("--examples-file")
+        .arg("llms-examples.txt")
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let llms = fs::read_to_string(project_path.join("llms.txt")).expect("Failed to read llms.txt");
+    assert!(llms.contains("- [Doc Examples](llms-examples.txt): "));
+
+    // llms-full.txt にはモジュールとアイテムごとのコードブロックの一覧を含める
+    let full = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+    let index = &full[full
+        .find("## Doc Examples\n\n")
+        .expect("no Doc Examples section")..];
+    assert!(
+        index.contains("### crate\n\n```rust\nlet total = doctest_project::add(1, 2);\n```\n\n")
+    );
+    assert!(index.contains("### add\n\n```rust\nassert_eq!(add(2, 2), 4);\n```\n\n"));
+    assert!(index.contains("### serve\n\n*`no_run`: compiled but not run*\n\n```rust\n"));
+    assert!(index.contains(
+        "### fail\n\n*`should_panic`: expected to panic*\n\n```rust\ndoctest_project::fail();\n```\n\n\
+         *`ignore`: not compiled or run as a test*\n\n```rust\nnot_compiled();\n```\n\n"
+    ));
+    // コード例の字下げは保つ
+    assert!(index.contains(
+        "### evens\n\n```rust\nfor n in 0..10 {\n    if n % 2 == 0 {\n        println!(\"{}\", n);\n    }\n}\n```\n"
+    ));
+    // 隠し行と Rust 以外のコードブロックは含めない
+    assert!(!index.contains("use doctest_project::add"));
+    assert!(!index.contains("[dependencies]"));
+    assert!(!index.contains("### plain"));
+
+    // --examples-file ではコードブロックだけのファイルも出力する
+    let examples = fs::read_to_string(project_path.join("llms-examples.txt"))
+        .expect("Failed to read llms-examples.txt");
+    assert!(examples.starts_with(
+        "# doctest_project - Doc Examples\n\n> A project with doc examples\n\n## crate\n\n"
+    ));
+    assert!(examples.contains("## add\n\n```rust\nassert_eq!(add(2, 2), 4);\n```\n"));
+    assert!(examples.contains("        println!(\"{}\", n);\n    }\n}\n```\n"));
+    assert!(!examples.contains("Generated:"));
+}
+
+#[test]
+fn test_proc_macro_crate() {
+    let project_path = Path::new("tests/fixtures/proc_macro_project");
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // 目次には関数名ではなく呼び出し方を載せる
+    let llms = fs::read_to_string(project_path.join("llms.txt")).expect("Failed to read llms.txt");
+    assert!(llms.contains("- hashmap!\n"));
+    assert!(llms.contains("- #[trace]\n"));
+    assert!(llms.contains("- #[derive(Builder)]\n"));
+    assert!(!llms.contains("derive_builder"));
+
+    let full = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+    assert!(full.contains(
+        "### hashmap!\n\n```rust\nhashmap!(...)\n```\n\nBuilds a `HashMap` from `key => value` pairs."
+    ));
+    assert!(full.contains("### #[trace]\n\n```rust\n#[trace(...)]\n```\n"));
+    assert!(full.contains(
+        "### #[derive(Builder)]\n\n```rust\n#[derive(Builder)]\n{\n    \
+         // Attributes available to this derive:\n    #[builder]\n}\n```\n\n\
+         Derives a builder for the annotated struct."
+    ));
+    assert!(!full.contains("pub fn derive_builder"));
+    assert!(!full.contains("fn expand"));
+}
+
+#[test]
+fn test_multiple_targets() {
+    let project_path = Path::new("tests/fixtures/targets_project");
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // ライブラリ以外のクレートルートの見出しにはターゲットを添える
+    let full = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+    assert!(full.contains("## crate\n\n*Source: `src/lib.rs`*\n\n"));
+    assert!(full.contains("## crate (bin main)\n\n*Source: `src/main.rs`*\n\n"));
+    assert!(full.contains("## crate (bin tool)\n\n*Source: `src/bin/tool.rs`*\n\n"));
+    assert_eq!(full.matches("## crate\n").count(), 1);
+}
+
+#[test]
+fn test_included_crate_docs_summary() {
+    let project_path = Path::new("tests/fixtures/readme_docs_project");
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-llms-txt"))
+        .arg("--path")
+        .arg(project_path)
+        .output()
+        .expect("Failed to execute cargo-llms-txt");
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // include_str! で読み込んだ docs も概要に使う。README の先頭の見出しは飛ばす
+    let llms = fs::read_to_string(project_path.join("llms.txt")).expect("Failed to read llms.txt");
+    assert!(llms.contains("# readme_docs_project\n\n> Parses things quickly.\n\n"));
+    assert!(llms.contains("### src/util.rs\n\nHelpers for parsing.\n\n- pub fn util::trim\n"));
+
+    let full = fs::read_to_string(project_path.join("llms-full.txt"))
+        .expect("Failed to read llms-full.txt");
+    assert!(full.contains("> Parses things quickly.\n\n"));
+}